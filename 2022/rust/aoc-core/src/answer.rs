@@ -0,0 +1,53 @@
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+/// A part's result, whatever type the solution computes it as.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// A picture to read the answer from, one string per row.
+    Image(Vec<String>),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => n.fmt(f),
+            Answer::Text(text) => f.write_str(text),
+            Answer::Image(rows) => f.write_str(&rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! integer_answers {
+    ($($int:ty),*) => {
+        $(impl From<$int> for Answer {
+            fn from(n: $int) -> Self {
+                Answer::Integer(n.into())
+            }
+        })*
+    };
+}
+
+integer_answers!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Integer(n as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt::{self, Display};
+
+use nom::error::ErrorKind;
+
+/// A problem in the puzzle input, located by line and column (both 1-based).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The full line containing the bad input.
+    pub snippet: String,
+    pub message: String,
+    width: usize,
+}
+
+impl ParseError {
+    /// Builds an error pointing at `span`, which must be a slice of `source`.
+    pub fn at(source: &str, span: &str, message: impl Into<String>) -> Self {
+        let offset = (span.as_ptr() as usize)
+            .checked_sub(source.as_ptr() as usize)
+            .filter(|offset| offset + span.len() <= source.len())
+            .expect("span is not a slice of source");
+
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |i| offset + i);
+        let snippet = source[line_start..line_end].trim_end_matches('\r');
+
+        let column = source[line_start..offset].chars().count() + 1;
+        let width = span
+            .lines()
+            .next()
+            .map_or(0, |first| first.trim_end_matches('\r').chars().count())
+            .max(1);
+
+        ParseError {
+            line: source[..offset].matches('\n').count() + 1,
+            column,
+            snippet: snippet.to_string(),
+            message: message.into(),
+            width,
+        }
+    }
+
+    /// Multi-line diagnostic that underlines the bad input.
+    pub fn render(&self) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let padding = " ".repeat(self.column - 1);
+        let underline = "^".repeat(self.width);
+
+        format!(
+            "{self}\n{gutter} |\n{} | {}\n{gutter} | {padding}{underline}",
+            self.line, self.snippet
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs the outcome of a nom parser over the whole of `source` into a
+/// located error. Anything left over apart from trailing whitespace is an error.
+pub fn finish<'a, O>(source: &'a str, result: nom::IResult<&'a str, O>) -> Result<O, ParseError> {
+    match result {
+        Ok((rest, output)) => {
+            let rest = rest.trim_start();
+            if rest.is_empty() {
+                Ok(output)
+            } else {
+                Err(ParseError::at(source, token(rest), "unexpected input"))
+            }
+        }
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => {
+            let message = if error.input.is_empty() {
+                "unexpected end of input"
+            } else {
+                describe(error.code)
+            };
+            Err(ParseError::at(source, token(error.input), message))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(
+            source,
+            &source[source.len()..],
+            "unexpected end of input",
+        )),
+    }
+}
+
+/// The word at the start of `input`, used as the span to underline.
+fn token(input: &str) -> &str {
+    let end = input.find(char::is_whitespace).unwrap_or(input.len());
+    &input[..end]
+}
+
+fn describe(code: ErrorKind) -> &'static str {
+    match code {
+        ErrorKind::Digit => "expected a number",
+        ErrorKind::Alpha => "expected letters",
+        ErrorKind::Char | ErrorKind::OneOf => "unexpected character",
+        ErrorKind::Tag => "unexpected token",
+        ErrorKind::MultiSpace | ErrorKind::Space => "expected whitespace",
+        ErrorKind::CrLf => "expected a line break",
+        ErrorKind::Eof => "expected end of input",
+        _ => "invalid input",
+    }
+}
+
+/// Why a part could not be solved.
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    /// The input parsed, but it has no answer.
+    Solve(String),
+}
+
+impl Error {
+    pub fn solve(message: impl Into<String>) -> Self {
+        Error::Solve(message.into())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(error) => error.fmt(f),
+            Error::Solve(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
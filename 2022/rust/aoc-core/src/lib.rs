@@ -0,0 +1,163 @@
+use std::fmt::{self, Display};
+
+pub use answer::Answer;
+pub use error::{finish, Error, ParseError, Result};
+
+mod answer;
+mod error;
+
+/// Tracing target of the step-by-step narration printed by `aoc --explain`.
+pub const EXPLAIN: &str = "explain";
+
+/// A day's puzzle: the input is parsed once and both parts solve from that model.
+/// The model is shared with the thread each part runs on, hence `Send + Sync`.
+pub trait Solution {
+    type Input<'a>: Send + Sync;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Part1>;
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Part2>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = u8;
+
+    fn try_from(part: u8) -> Result<Self, Self::Error> {
+        match part {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            other => Err(other),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Object-safe view of a [`Solution`], so a runner can keep every day in one table.
+pub trait DynSolution: Sync {
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, ParseError>;
+}
+
+/// A parsed input, ready to solve either part.
+pub trait Parsed: Send + Sync {
+    fn solve(&self, part: Part) -> Result<Answer>;
+}
+
+struct Model<'a, S: Solution>(S::Input<'a>);
+
+impl<S: Solution> Parsed for Model<'_, S> {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => S::part_1(&self.0).map(Into::into),
+            Part::Two => S::part_2(&self.0).map(Into::into),
+        }
+    }
+}
+
+impl<S: Solution + Sync + 'static> DynSolution for S {
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, ParseError> {
+        Ok(Box::new(Model::<S>(S::parse(input)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Words;
+
+    impl Solution for Words {
+        type Input<'a> = Vec<&'a str>;
+        type Part1 = usize;
+        type Part2 = String;
+
+        fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+            Ok(input.split_whitespace().collect())
+        }
+
+        fn part_1(words: &Vec<&str>) -> Result<usize> {
+            Ok(words.len())
+        }
+
+        fn part_2(words: &Vec<&str>) -> Result<String> {
+            Ok(words.concat())
+        }
+    }
+
+    #[test]
+    fn test_dyn_solution() {
+        let solution: &dyn DynSolution = &Words;
+        let parsed = solution.parse("a bc d").unwrap();
+
+        assert_eq!(parsed.solve(Part::One).unwrap(), Answer::Integer(3));
+        assert_eq!(parsed.solve(Part::Two).unwrap().to_string(), "abcd");
+    }
+
+    #[test]
+    fn test_answer_json() {
+        let image = Answer::Image(vec!["#.".to_string(), ".#".to_string()]);
+
+        assert_eq!(image.to_string(), "#.\n.#");
+        assert_eq!(
+            serde_json::to_string(&image).unwrap(),
+            r##"{"type":"image","value":["#.",".#"]}"##
+        );
+        assert_eq!(
+            serde_json::to_string(&Answer::from(24000u32)).unwrap(),
+            r#"{"type":"integer","value":24000}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let source = "1-2\n3-x4\n";
+        let error = ParseError::at(source, &source[6..8], "expected a number");
+
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.snippet, "3-x4");
+        assert_eq!(
+            error.render(),
+            "line 2, column 3: expected a number\n  |\n2 | 3-x4\n  |   ^^"
+        );
+    }
+
+    #[test]
+    fn test_finish_rejects_trailing_input() {
+        use nom::{character::complete, multi::separated_list1};
+
+        let source = "1,2\n3,x\n";
+        let result = separated_list1(complete::newline, |input| {
+            separated_list1(complete::char(','), complete::u32)(input)
+        })(source);
+        let error = finish(source, result).unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.message, "unexpected input");
+    }
+}
@@ -0,0 +1,60 @@
+//! Parse and part timings for every checked-in input (`input.txt`, `input_1.txt`, ...).
+//!
+//! Save a baseline before a change and compare against it afterwards; criterion
+//! reports "Performance has regressed" for any bench that got measurably slower:
+//!
+//! ```text
+//! cargo bench -p aoc -- --save-baseline before
+//! cargo bench -p aoc -- --baseline before
+//! ```
+//!
+//! Filter to one day with e.g. `cargo bench -p aoc -- 2022/day-8`.
+
+use std::{fs, hint::black_box, path::PathBuf};
+
+use aoc::{days::DAYS, input::workspace_root};
+use aoc_core::Part;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn inputs(dir: &str) -> Vec<PathBuf> {
+    let mut inputs: Vec<PathBuf> = fs::read_dir(workspace_root().join(dir))
+        .unwrap_or_else(|error| panic!("failed to list {dir}: {error}"))
+        .map(|entry| entry.expect("failed to read directory entry").path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("input") && name.ends_with(".txt"))
+        })
+        .collect();
+    inputs.sort();
+    inputs
+}
+
+fn days(c: &mut Criterion) {
+    for day in DAYS {
+        let mut group = c.benchmark_group(format!("{}/{}", day.year, day.dir()));
+
+        for path in inputs(day.dir()) {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            let input = fs::read_to_string(&path)
+                .unwrap_or_else(|error| panic!("failed to read {}: {error}", path.display()));
+            let parsed = day.solution.parse(&input).unwrap_or_else(|error| {
+                panic!("failed to parse {}\n{}", path.display(), error.render())
+            });
+
+            group.bench_function(format!("{name}/parse"), |b| {
+                b.iter(|| day.solution.parse(black_box(&input)).map(|_| ()))
+            });
+            for part in Part::BOTH {
+                group.bench_function(format!("{name}/part_{part}"), |b| {
+                    b.iter(|| parsed.solve(black_box(part)))
+                });
+            }
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
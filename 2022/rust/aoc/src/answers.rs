@@ -0,0 +1,132 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use aoc_core::Part;
+use serde::Deserialize;
+
+/// Confirmed answers, relative to the workspace root.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Answers that were accepted by the puzzle site, keyed by year, day and the
+/// input's SHA-256.
+#[derive(Debug, Default, Deserialize)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Entry {
+    year: u16,
+    day: u8,
+    /// Hex SHA-256 of the input, see [`crate::report::input_sha256`].
+    input_sha256: String,
+    part_1: Option<String>,
+    part_2: Option<String>,
+}
+
+/// How a computed answer compares with the registry.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::parse(&source).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn parse(source: &str) -> Result<Self> {
+        Ok(toml::from_str(source)?)
+    }
+
+    pub fn get(&self, year: u16, day: u8, input_sha256: &str, part: Part) -> Option<&str> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|e| e.year == year && e.day == day && e.input_sha256 == input_sha256)?;
+
+        match part {
+            Part::One => entry.part_1.as_deref(),
+            Part::Two => entry.part_2.as_deref(),
+        }
+    }
+
+    /// Multi-line answers may end with a line break in the file, so trailing
+    /// whitespace is ignored.
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        input_sha256: &str,
+        part: Part,
+        answer: &str,
+    ) -> Verdict {
+        match self.get(year, day, input_sha256, part) {
+            None => Verdict::Unknown,
+            Some(expected) if expected.trim_end() == answer.trim_end() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+[[answer]]
+year = 2022
+day = 1
+input_sha256 = "aaaa"
+part_1 = "24000"
+
+[[answer]]
+year = 2022
+day = 10
+input_sha256 = "bbbb"
+part_2 = '''
+##..
+..##
+'''
+"#;
+
+    #[test]
+    fn test_lookup() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(answers.get(2022, 1, "aaaa", Part::One), Some("24000"));
+        assert_eq!(answers.get(2022, 1, "aaaa", Part::Two), None);
+        assert_eq!(answers.get(2022, 1, "cccc", Part::One), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(
+            answers.check(2022, 1, "aaaa", Part::One, "24000"),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(2022, 1, "aaaa", Part::One, "45000"),
+            Verdict::Fail {
+                expected: "24000".to_string()
+            }
+        );
+        assert_eq!(
+            answers.check(2022, 10, "bbbb", Part::Two, "##..\n..##"),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(2022, 2, "aaaa", Part::One, "15"),
+            Verdict::Unknown
+        );
+    }
+}
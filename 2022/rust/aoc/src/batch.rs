@@ -0,0 +1,174 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use aoc_core::{Answer, Part};
+use rayon::prelude::*;
+
+use crate::{days::Day, isolate::guarded, timing};
+
+/// One part solved from one input file.
+#[derive(Debug)]
+pub struct Row {
+    pub file: PathBuf,
+    pub part: Part,
+    pub answer: Answer,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+/// An input that could not be read, parsed or solved, or that panicked.
+#[derive(Debug)]
+pub struct Failure {
+    pub file: PathBuf,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub rows: Vec<Row>,
+    pub failures: Vec<Failure>,
+}
+
+impl Report {
+    /// `file,part,answer,parse_us,solve_us`, one line per solved part.
+    pub fn csv(&self) -> String {
+        let mut csv = String::from("file,part,answer,parse_us,solve_us\n");
+        for row in &self.rows {
+            csv += &format!(
+                "{},{},{},{},{}\n",
+                field(&row.file.display().to_string()),
+                row.part,
+                field(&row.answer.to_string()),
+                row.parse.as_micros(),
+                row.solve.as_micros()
+            );
+        }
+        csv
+    }
+}
+
+/// Quotes a CSV field if it needs it; image answers span several lines.
+fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Solves `parts` for every file in `dir`, in parallel. Rows and failures are
+/// sorted by file name.
+pub fn run(day: &Day, dir: &Path, parts: &[Part]) -> Result<Report> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to list {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    let reports: Vec<Report> = files
+        .par_iter()
+        .map(|file| solve_file(day, file, parts))
+        .collect();
+
+    Ok(reports
+        .into_iter()
+        .fold(Report::default(), |mut all, report| {
+            all.rows.extend(report.rows);
+            all.failures.extend(report.failures);
+            all
+        }))
+}
+
+fn solve_file(day: &Day, file: &Path, parts: &[Part]) -> Report {
+    let mut report = Report::default();
+    let failure = |reason: String| Failure {
+        file: file.to_path_buf(),
+        reason,
+    };
+
+    let input = match fs::read_to_string(file) {
+        Ok(input) => input,
+        Err(error) => {
+            report
+                .failures
+                .push(failure(format!("failed to read: {error}")));
+            return report;
+        }
+    };
+
+    let (parsed, parse) = timing::time(|| guarded(|| day.solution.parse(&input)));
+    let parsed = match parsed {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(error)) => {
+            report
+                .failures
+                .push(failure(format!("failed to parse: {error}")));
+            return report;
+        }
+        Err(panic) => {
+            report
+                .failures
+                .push(failure(format!("panicked while parsing: {panic}")));
+            return report;
+        }
+    };
+
+    for &part in parts {
+        let (answer, solve) = timing::time(|| guarded(|| parsed.solve(part)));
+        match answer {
+            Ok(Ok(answer)) => report.rows.push(Row {
+                file: file.to_path_buf(),
+                part,
+                answer,
+                parse,
+                solve,
+            }),
+            Ok(Err(error)) => report
+                .failures
+                .push(failure(format!("part {part}: {error}"))),
+            Err(panic) => report
+                .failures
+                .push(failure(format!("part {part} panicked: {panic}"))),
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_run_reports_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.txt"), "1000\n2000\n\n500\n").unwrap();
+        fs::write(dir.path().join("b.txt"), "1000\nlots\n").unwrap();
+        fs::create_dir(dir.path().join("nested")).unwrap();
+
+        let day = days::find(2022, 1).unwrap();
+        let report = run(day, dir.path(), &[Part::One]).unwrap();
+
+        assert_eq!(report.rows.len(), 1);
+        assert_eq!(report.rows[0].answer, Answer::Integer(3000));
+        assert_eq!(report.failures.len(), 1);
+        assert!(report.failures[0].file.ends_with("b.txt"));
+        assert!(report.failures[0]
+            .reason
+            .starts_with("failed to parse: line 2"));
+    }
+
+    #[test]
+    fn test_field_quoting() {
+        assert_eq!(field("day-1/input.txt"), "day-1/input.txt");
+        assert_eq!(field("a,\"b\""), "\"a,\"\"b\"\"\"");
+        assert_eq!(field("#..\n.#."), "\"#..\n.#.\"");
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+/// Overrides where the config file is read from.
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+
+/// Settings for talking to adventofcode.com, read from
+/// `~/.config/aoc/config.toml` unless `AOC_CONFIG` points elsewhere.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Config {
+    /// Value of the site's `session` cookie.
+    pub session: String,
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    /// Where downloaded inputs are kept, `~/.cache/aoc` by default.
+    pub cache_dir: Option<PathBuf>,
+    /// Minimum time between two requests to the site.
+    #[serde(default = "default_request_interval_secs")]
+    pub request_interval_secs: f64,
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        let path = match env::var_os(CONFIG_VAR) {
+            Some(path) => PathBuf::from(path),
+            None => config_home()?.join("aoc").join("config.toml"),
+        };
+        Self::from_file(&path)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(path).with_context(|| {
+            format!(
+                "failed to read {}, it needs at least `session = \"<cookie>\"`",
+                path.display()
+            )
+        })?;
+        let config: Config = toml::from_str(&source)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+
+        if config.session.trim().is_empty() {
+            bail!("the session token in {} is empty", path.display());
+        }
+        Ok(config)
+    }
+
+    pub fn cache_dir(&self) -> Result<PathBuf> {
+        match &self.cache_dir {
+            Some(dir) => Ok(dir.clone()),
+            None => Ok(cache_home()?.join("aoc")),
+        }
+    }
+
+    pub fn request_interval(&self) -> Duration {
+        Duration::from_secs_f64(self.request_interval_secs.max(0.0))
+    }
+}
+
+fn default_base_url() -> String {
+    "https://adventofcode.com".to_string()
+}
+
+fn default_request_interval_secs() -> f64 {
+    5.0
+}
+
+fn config_home() -> Result<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+fn cache_home() -> Result<PathBuf> {
+    xdg_dir("XDG_CACHE_HOME", ".cache")
+}
+
+fn xdg_dir(var: &str, fallback: &str) -> Result<PathBuf> {
+    if let Some(dir) = env::var_os(var).filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    let home = env::var_os("HOME").context("neither $HOME nor ${var} is set")?;
+    Ok(PathBuf::from(home).join(fallback))
+}
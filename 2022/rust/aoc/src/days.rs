@@ -0,0 +1,79 @@
+use aoc_core::DynSolution;
+
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    /// Input file, relative to the workspace root.
+    pub input: &'static str,
+    pub solution: &'static dyn DynSolution,
+}
+
+impl Day {
+    /// The day's crate directory, relative to the workspace root.
+    pub fn dir(&self) -> &'static str {
+        self.input.split_once('/').map_or(".", |(dir, _)| dir)
+    }
+}
+
+const fn day(year: u16, day: u8, input: &'static str, solution: &'static dyn DynSolution) -> Day {
+    Day {
+        year,
+        day,
+        input,
+        solution,
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    day(2022, 1, "day-1/input_1.txt", &day_1::Day1),
+    day(2022, 2, "day-2/input_1.txt", &day_2::Day2),
+    day(2022, 3, "day-3/input_1.txt", &day_3::Day3),
+    day(2022, 4, "day-4/input_1.txt", &day_4::Day4),
+    day(2022, 5, "day-5/input_1.txt", &day_5::Day5),
+    day(2022, 6, "day-6/input.txt", &day_6::Day6),
+    day(2022, 7, "day-7/input.txt", &day_7::Day7),
+    day(2022, 8, "day-8/input.txt", &day_8::Day8),
+    day(2022, 9, "day-9/input.txt", &day_9::Day9),
+    day(2022, 10, "day-10/input.txt", &day_10::Day10),
+    day(2022, 11, "day-11/input.txt", &day_11::Day11),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Part;
+
+    use super::*;
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for day in DAYS {
+            // A freshly scaffolded day has no generator yet.
+            if generators::generator(day.year, day.day).is_none() {
+                continue;
+            }
+            // Small sizes catch generators that leave a record empty.
+            for (size, seed) in [1, 10, 30]
+                .into_iter()
+                .flat_map(|size| (0..25).map(move |seed| (size, seed)))
+            {
+                let input = generators::generate(day.year, day.day, seed, size)
+                    .expect("the day has a generator");
+                let context = format!("day {} size {size} seed {seed}", day.day);
+                let parsed = day
+                    .solution
+                    .parse(&input)
+                    .unwrap_or_else(|error| panic!("{context}\n{}\n{input}", error.render()));
+
+                for part in Part::BOTH {
+                    if let Err(error) = parsed.solve(part) {
+                        panic!("{context} part {part}: {error}\n{input}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    fmt::{self, Write as _},
+    io::Write,
+    sync::Mutex,
+};
+
+use aoc_core::EXPLAIN;
+use tracing::{
+    field::{Field, Visit},
+    Event, Metadata, Subscriber,
+};
+use tracing_subscriber::{filter::FilterFn, layer::Context, Layer};
+
+/// Writes the message of every [`EXPLAIN`] event as a plain line of text.
+pub struct Narrator {
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl Narrator {
+    pub fn new(out: impl Write + Send + 'static) -> Self {
+        Narrator {
+            out: Mutex::new(Box::new(out)),
+        }
+    }
+
+    /// Only explain events reach the narrator, so other events stay disabled.
+    pub fn filter() -> FilterFn {
+        FilterFn::new(is_explain as fn(&Metadata<'_>) -> bool)
+    }
+
+    /// Everything but explain events, for a log that runs beside the narrator.
+    pub fn others() -> FilterFn {
+        FilterFn::new((|metadata| !is_explain(metadata)) as fn(&Metadata<'_>) -> bool)
+    }
+}
+
+impl<S: Subscriber> Layer<S> for Narrator {
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        let mut line = Message(String::new());
+        event.record(&mut line);
+
+        let mut out = self.out.lock().expect("narration output is not poisoned");
+        // Narration is best effort; a closed pipe should not stop the solution.
+        let _ = writeln!(out, "{}", line.0);
+    }
+}
+
+fn is_explain(metadata: &Metadata<'_>) -> bool {
+    metadata.target() == EXPLAIN
+}
+
+struct Message(String);
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, "{value:?}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::Arc};
+
+    use aoc_core::{Part, Solution};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+    use crate::days;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(bytes)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_narrates_explain_events_only() {
+        let buffer = Buffer::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(Narrator::new(buffer.clone()).with_filter(Narrator::filter()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!("not narrated");
+            let elves = day_1::Day1::parse("1000\n\n5000\n6000\n\n3000").unwrap();
+            let parsed = days::find(2022, 1)
+                .unwrap()
+                .solution
+                .parse("7\n\n8")
+                .unwrap();
+
+            day_1::Day1::part_1(&elves).unwrap();
+            parsed.solve(Part::Two).unwrap();
+        });
+
+        assert_eq!(
+            String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap(),
+            "elf 2 carries the most, 11000 calories\n#1: elf 2 with 8 calories\n#2: elf 1 with 7 calories\n"
+        );
+    }
+}
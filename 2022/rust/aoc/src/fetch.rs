@@ -0,0 +1,167 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::SystemTime,
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{config::Config, http::Transport};
+
+/// Where an input ended up, and whether it had to be downloaded.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    Cached(PathBuf),
+}
+
+impl Fetched {
+    pub fn path(&self) -> &Path {
+        match self {
+            Fetched::Downloaded(path) | Fetched::Cached(path) => path,
+        }
+    }
+}
+
+/// The cached input of `year` and `day`: `<cache_dir>/<year>/day-<day>.txt`.
+pub fn cache_path(cache_dir: &Path, year: u16, day: u8) -> PathBuf {
+    cache_dir
+        .join(year.to_string())
+        .join(format!("day-{day}.txt"))
+}
+
+/// Downloads a day's input into the cache, unless it is already there.
+pub fn fetch(config: &Config, transport: &dyn Transport, year: u16, day: u8) -> Result<Fetched> {
+    let cache_dir = config.cache_dir()?;
+    let path = cache_path(&cache_dir, year, day);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let url = format!("{}/{year}/day/{day}/input", config.base_url);
+    let response = throttled(config, &cache_dir, || transport.get(&url, &config.session))?;
+
+    match response.status {
+        200 => {}
+        404 => bail!("{year} day {day} is not unlocked yet"),
+        400 | 401 | 403 | 500 if response.body.contains("log in") => {
+            bail!("the site rejected the session token, it may have expired")
+        }
+        status => bail!(
+            "{url} answered with status {status}: {}",
+            response.body.trim()
+        ),
+    }
+
+    let dir = path.parent().expect("cache paths have a year directory");
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    // Write next to the final file and rename, so an interrupted download
+    // never looks cached.
+    let partial = path.with_extension("txt.partial");
+    fs::write(&partial, &response.body)
+        .with_context(|| format!("failed to write {}", partial.display()))?;
+    fs::rename(&partial, &path).with_context(|| format!("failed to write {}", path.display()))?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+/// Runs `request` no sooner than `request_interval` after the previous one,
+/// remembered across runs by the modification time of a stamp file.
+pub(crate) fn throttled<T>(
+    config: &Config,
+    cache_dir: &Path,
+    request: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    let stamp = cache_dir.join(".last-request");
+    let last = fs::metadata(&stamp).and_then(|meta| meta.modified()).ok();
+    if let Some(elapsed) = last.and_then(|last| SystemTime::now().duration_since(last).ok()) {
+        if let Some(wait) = config.request_interval().checked_sub(elapsed) {
+            thread::sleep(wait);
+        }
+    }
+
+    let result = request();
+
+    fs::create_dir_all(cache_dir)
+        .with_context(|| format!("failed to create {}", cache_dir.display()))?;
+    fs::write(&stamp, b"").with_context(|| format!("failed to write {}", stamp.display()))?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::http::{mock::Server, Ureq};
+
+    fn config(server: &Server, cache_dir: &Path, interval: f64) -> Config {
+        Config {
+            session: "53616c7465".to_string(),
+            base_url: server.url.clone(),
+            cache_dir: Some(cache_dir.to_path_buf()),
+            request_interval_secs: interval,
+        }
+    }
+
+    #[test]
+    fn test_fetch_downloads_once() {
+        let server = Server::start(|_| (200, "1000\n2000\n".to_string()));
+        let cache = tempfile::tempdir().unwrap();
+        let config = config(&server, cache.path(), 0.0);
+
+        let first = fetch(&config, &Ureq::default(), 2022, 1).unwrap();
+        let second = fetch(&config, &Ureq::default(), 2022, 1).unwrap();
+
+        let path = cache.path().join("2022").join("day-1.txt");
+        assert_eq!(first, Fetched::Downloaded(path.clone()));
+        assert_eq!(second, Fetched::Cached(path.clone()));
+        assert_eq!(fs::read_to_string(path).unwrap(), "1000\n2000\n");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2022/day/1/input "));
+        assert!(requests[0]
+            .to_lowercase()
+            .contains("cookie: session=53616c7465"));
+    }
+
+    #[test]
+    fn test_fetch_errors_are_not_cached() {
+        let server = Server::start(|request| {
+            if request.contains("/day/25/") {
+                (404, "Not Found".to_string())
+            } else {
+                (
+                    400,
+                    "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+                        .to_string(),
+                )
+            }
+        });
+        let cache = tempfile::tempdir().unwrap();
+        let config = config(&server, cache.path(), 0.0);
+
+        let error = fetch(&config, &Ureq::default(), 2022, 25).unwrap_err();
+        assert_eq!(error.to_string(), "2022 day 25 is not unlocked yet");
+
+        let error = fetch(&config, &Ureq::default(), 2022, 2).unwrap_err();
+        assert!(error.to_string().contains("rejected the session token"));
+        assert!(!cache_path(cache.path(), 2022, 2).exists());
+    }
+
+    #[test]
+    fn test_requests_are_spaced_out() {
+        let server = Server::start(|_| (200, "input".to_string()));
+        let cache = tempfile::tempdir().unwrap();
+        let config = config(&server, cache.path(), 0.3);
+
+        let start = Instant::now();
+        fetch(&config, &Ureq::default(), 2022, 1).unwrap();
+        fetch(&config, &Ureq::default(), 2022, 2).unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert_eq!(server.requests().len(), 2);
+    }
+}
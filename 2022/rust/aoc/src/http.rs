@@ -0,0 +1,136 @@
+use std::io::Read;
+
+use anyhow::{Context, Result};
+
+const USER_AGENT: &str = "github.com/nei7/advent_of_code aoc runner";
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// The requests made to the puzzle site, so tests can point them at a local server.
+pub trait Transport {
+    fn get(&self, url: &str, session: &str) -> Result<Response>;
+    /// Posts `form` URL-encoded.
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response>;
+}
+
+/// Blocking HTTP(S) client used outside of tests.
+pub struct Ureq {
+    agent: ureq::Agent,
+}
+
+impl Default for Ureq {
+    fn default() -> Self {
+        Ureq {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+}
+
+impl Transport for Ureq {
+    fn get(&self, url: &str, session: &str) -> Result<Response> {
+        let request = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={session}"));
+        response(request.call(), url)
+    }
+
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response> {
+        let request = self
+            .agent
+            .post(url)
+            .set("Cookie", &format!("session={session}"));
+        response(request.send_form(form), url)
+    }
+}
+
+/// Error statuses are answers from the site, not transport failures.
+fn response(result: Result<ureq::Response, ureq::Error>, url: &str) -> Result<Response> {
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(error) => return Err(error).with_context(|| format!("request to {url} failed")),
+    };
+
+    let status = response.status();
+    let mut body = String::new();
+    response
+        .into_reader()
+        .read_to_string(&mut body)
+        .with_context(|| format!("failed to read the response from {url}"))?;
+    Ok(Response { status, body })
+}
+
+/// A stand-in for the puzzle site that answers on localhost.
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    pub struct Server {
+        pub url: String,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Server {
+        /// Answers every request with `respond(request)`, where the request is
+        /// the raw head and body as text.
+        pub fn start(respond: impl Fn(&str) -> (u16, String) + Send + 'static) -> Server {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+
+            let seen = Arc::clone(&requests);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let request = read_request(&mut BufReader::new(&stream));
+                    let (status, body) = respond(&request);
+                    seen.lock().unwrap().push(request);
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+                }
+            });
+
+            Server { url, requests }
+        }
+
+        pub fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn read_request(reader: &mut impl BufRead) -> String {
+        let mut request = String::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            request.push_str(&line);
+            if line == "\r\n" || line.is_empty() {
+                break;
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        request.push_str(&String::from_utf8(body).unwrap());
+        request
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    fmt::{self, Display},
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::days::Day;
+
+/// Directory laid out like this workspace (`day-7/input.txt`, ...) to read inputs from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a day's puzzle input is read from.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    /// A path given on the command line.
+    Path(PathBuf),
+    /// The day's input file under `AOC_INPUT_DIR`.
+    InputDir(PathBuf),
+    /// The input checked in next to the day's crate.
+    Workspace(PathBuf),
+}
+
+impl Source {
+    /// An explicit path wins (`-` meaning stdin), then `AOC_INPUT_DIR`,
+    /// then the checked-in input.
+    pub fn resolve(day: &Day, path: Option<&Path>, input_dir: Option<&Path>) -> Source {
+        match (path, input_dir) {
+            (Some(path), _) if path == Path::new("-") => Source::Stdin,
+            (Some(path), _) => Source::Path(path.to_path_buf()),
+            (None, Some(dir)) => Source::InputDir(dir.join(day.input)),
+            (None, None) => Source::Workspace(workspace_root().join(day.input)),
+        }
+    }
+
+    pub fn read(&self) -> Result<String> {
+        let path = match self {
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("failed to read input from stdin")?;
+                return Ok(input);
+            }
+            Source::Path(path) | Source::InputDir(path) | Source::Workspace(path) => path,
+        };
+
+        match fs::read_to_string(path) {
+            Ok(input) => Ok(input),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => bail!(self.not_found()),
+            Err(error) => Err(error).with_context(|| format!("failed to read {self}")),
+        }
+    }
+
+    fn not_found(&self) -> String {
+        match self {
+            Source::Stdin => unreachable!("stdin always exists"),
+            Source::Path(_) => format!("input file {self} does not exist"),
+            Source::InputDir(_) => {
+                format!("input file {self} does not exist (resolved from {INPUT_DIR_VAR})")
+            }
+            Source::Workspace(_) => format!(
+                "input file {self} does not exist, pass --input <PATH> or set {INPUT_DIR_VAR}"
+            ),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Stdin => f.write_str("<stdin>"),
+            Source::Path(path) | Source::InputDir(path) | Source::Workspace(path) => {
+                path.display().fmt(f)
+            }
+        }
+    }
+}
+
+/// The directory holding every day's crate.
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner lives inside the workspace")
+        .to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_resolve_precedence() {
+        let day = days::find(2022, 7).unwrap();
+        let dir = Path::new("/inputs");
+
+        assert_eq!(
+            Source::resolve(day, Some(Path::new("-")), Some(dir)),
+            Source::Stdin
+        );
+        assert_eq!(
+            Source::resolve(day, Some(Path::new("mine.txt")), Some(dir)),
+            Source::Path(PathBuf::from("mine.txt"))
+        );
+        assert_eq!(
+            Source::resolve(day, None, Some(dir)),
+            Source::InputDir(PathBuf::from("/inputs/day-7/input.txt"))
+        );
+        assert_eq!(
+            Source::resolve(day, None, None),
+            Source::Workspace(workspace_root().join("day-7/input.txt"))
+        );
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let day = days::find(2022, 7).unwrap();
+        let source = Source::resolve(day, None, Some(Path::new("/does/not/exist")));
+
+        assert_eq!(
+            source.read().unwrap_err().to_string(),
+            "input file /does/not/exist/day-7/input.txt does not exist (resolved from AOC_INPUT_DIR)"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Once,
+    },
+    thread,
+    time::Duration,
+};
+
+thread_local! {
+    /// Whether this thread is inside `guarded`, whose callers report panics
+    /// themselves.
+    static GUARDED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// How a guarded piece of work ended.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome<T> {
+    Done(T),
+    /// With the panic's message.
+    Panicked(String),
+    TimedOut,
+}
+
+/// Runs `f` on its own thread, giving up on it after `timeout`. A thread
+/// that overruns cannot be stopped, so it is left to run until the process
+/// exits.
+pub fn run<T: Send + 'static>(
+    timeout: Option<Duration>,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Outcome<T> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone if we already gave up on this thread.
+        let _ = sender.send(guarded(f));
+    });
+
+    let result = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match result {
+        Ok(Ok(output)) => Outcome::Done(output),
+        Ok(Err(message)) => Outcome::Panicked(message),
+        Err(RecvTimeoutError::Timeout) => Outcome::TimedOut,
+        Err(RecvTimeoutError::Disconnected) => unreachable!("the thread always sends"),
+    }
+}
+
+/// Runs `f`, turning a panic into its message. The panic hook stays quiet
+/// for it, since the caller reports the message itself.
+pub fn guarded<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    silence_guarded_panics();
+    let _guard = Guard::enter();
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message)
+}
+
+/// Wraps the panic hook once so that it skips panics on threads inside
+/// `guarded`, leaving every other panic to the previous hook.
+fn silence_guarded_panics() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !GUARDED.with(Cell::get) {
+                previous_hook(info);
+            }
+        }));
+    });
+}
+
+/// Marks this thread as guarded until dropped, even when unwinding.
+struct Guard {
+    was_guarded: bool,
+}
+
+impl Guard {
+    fn enter() -> Self {
+        Guard {
+            was_guarded: GUARDED.with(|guarded| guarded.replace(true)),
+        }
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        GUARDED.with(|guarded| guarded.set(self.was_guarded));
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_outcomes() {
+        let timeout = Some(Duration::from_secs(10));
+
+        assert_eq!(run(timeout, || 42), Outcome::Done(42));
+        assert_eq!(
+            run(timeout, || -> u32 { panic!("stack {} is empty", 3) }),
+            Outcome::Panicked("stack 3 is empty".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_times_out() {
+        let outcome = run(Some(Duration::from_millis(10)), || {
+            thread::sleep(Duration::from_secs(5));
+        });
+
+        assert_eq!(outcome, Outcome::TimedOut);
+    }
+
+    #[test]
+    fn test_guarded_catches_panics() {
+        let caught = guarded(|| -> u32 { panic!("stack {} is empty", 3) });
+
+        assert_eq!(caught, Err("stack 3 is empty".to_string()));
+    }
+
+    #[test]
+    fn test_guard_is_released_after_a_panic() {
+        assert!(guarded(|| panic!("quiet")).is_err());
+
+        assert!(!GUARDED.with(Cell::get));
+    }
+}
@@ -0,0 +1,16 @@
+pub mod answers;
+pub mod batch;
+pub mod config;
+pub mod days;
+pub mod explain;
+pub mod fetch;
+pub mod http;
+pub mod input;
+pub mod isolate;
+pub mod log;
+pub mod memory;
+pub mod report;
+pub mod scaffold;
+pub mod submit;
+pub mod timing;
+pub mod watch;
@@ -0,0 +1,82 @@
+use tracing::{level_filters::LevelFilter, Subscriber};
+use tracing_subscriber::{filter::FilterExt, fmt::MakeWriter, registry::LookupSpan, Layer};
+
+use crate::explain::Narrator;
+
+/// The `-v` log: progress at `-v`, every round at `-vv` and every step from
+/// `-vvv` on. Explain narration is left to the [`Narrator`].
+pub fn layer<S, W>(verbose: u8, writer: W) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let level = match verbose {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .without_time()
+        .with_filter(Narrator::others().and(level))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{self, Write},
+        sync::{Arc, Mutex},
+    };
+
+    use aoc_core::Part;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+    use crate::days;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(bytes)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn logged(verbose: u8) -> String {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber =
+            tracing_subscriber::registry().with(layer(verbose, move || writer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let parsed = days::find(2022, 1)
+                .unwrap()
+                .solution
+                .parse("7\n\n8")
+                .unwrap();
+            parsed.solve(Part::Two).unwrap();
+        });
+
+        let output = buffer.0.lock().unwrap().clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_verbosity_levels() {
+        assert_eq!(logged(0), "");
+
+        let progress = logged(1);
+        assert!(progress.contains("parsed snacks"));
+        assert!(!progress.contains("top_three"));
+
+        let rounds = logged(2);
+        assert!(rounds.contains("top_three"));
+        assert!(!rounds.contains("elf 2 with 8 calories"));
+    }
+}
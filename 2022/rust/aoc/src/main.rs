@@ -0,0 +1,651 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env,
+    fs::File,
+    io::{self, BufWriter},
+    iter,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use aoc::{
+    answers::{Answers, Verdict, ANSWERS_FILE},
+    batch,
+    config::Config,
+    days::{self, Day, DAYS},
+    explain::Narrator,
+    fetch::{self, Fetched},
+    http::Ureq,
+    input::{self, Source, INPUT_DIR_VAR},
+    isolate, log,
+    memory::{self, Counting, Usage},
+    report::{self, PartMemory, Record},
+    scaffold,
+    submit::{self, Outcome, Submitted},
+    timing::{self, Summary, Timings},
+    watch,
+};
+use aoc_core::{Answer, Parsed, Part};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use tracing::{info, info_span, Span};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Runs Advent of Code solutions, e.g. `aoc 2022 7 2` or `aoc --all`.
+#[derive(Parser)]
+#[command(
+    name = "aoc",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    run: Run,
+    /// Log what the solutions do to stderr: -v for progress, -vv for every
+    /// round, -vvv for every step
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Args)]
+struct Run {
+    /// Puzzle year
+    #[arg(required_unless_present = "all")]
+    year: Option<u16>,
+    /// Puzzle day, every day of the year when omitted
+    day: Option<u8>,
+    /// Puzzle part, both parts when omitted
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Run every day of every year
+    #[arg(long, conflicts_with = "year")]
+    all: bool,
+    /// Read the input from PATH instead, `-` for stdin
+    #[arg(short, long, value_name = "PATH", requires = "day")]
+    input: Option<PathBuf>,
+    /// How to print results: answer lines and timings, or a JSON record per part
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Narrate the steps behind each answer before printing it
+    #[arg(long, conflicts_with = "format")]
+    explain: bool,
+    /// Report the allocations, bytes allocated and peak live memory of
+    /// parsing and of each part
+    #[arg(long)]
+    memory: bool,
+    /// Give up on a part after SECONDS and carry on with the rest, 0 for no limit
+    #[arg(long, value_name = "SECONDS", default_value = "60", value_parser = seconds)]
+    timeout: Duration,
+    /// Save the pictures the solution draws to PATH: every frame as an
+    /// animated `.gif`, or the last one as a `.png`
+    #[arg(long, value_name = "PATH", requires = "day")]
+    record: Option<PathBuf>,
+    /// Size in pixels of each cell in a recording
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..=64))]
+    scale: u16,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// How long each frame of a recorded GIF is shown, in hundredths of a second.
+const FRAME_DELAY: u16 = 5;
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check answers against answers.toml, every day when no year is given
+    Verify {
+        /// Puzzle year
+        year: Option<u16>,
+        /// Puzzle day, every day of the year when omitted
+        day: Option<u8>,
+        /// Give up on a part after SECONDS and carry on with the rest, 0 for no limit
+        #[arg(long, value_name = "SECONDS", default_value = "60", value_parser = seconds)]
+        timeout: Duration,
+    },
+    /// Download a puzzle input into the cache and print its path, e.g.
+    /// `aoc 2022 7 -i "$(aoc fetch 2022 7)"`
+    Fetch {
+        /// Puzzle year
+        year: u16,
+        /// Puzzle day
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Create a day crate from the template and register it in the workspace
+    New {
+        /// Puzzle year
+        year: u16,
+        /// Puzzle day
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Solve a part and post the answer, unless it is already known to be wrong
+    Submit {
+        /// Puzzle year
+        year: u16,
+        /// Puzzle day
+        day: u8,
+        /// Puzzle part
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Read the input from PATH instead, `-` for stdin
+        #[arg(short, long, value_name = "PATH")]
+        input: Option<PathBuf>,
+    },
+    /// Solve every file in a directory in parallel, printing a CSV of the
+    /// answers and listing the inputs that failed
+    Batch {
+        /// Puzzle year
+        year: u16,
+        /// Puzzle day
+        day: u8,
+        /// Puzzle part, both parts when omitted
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Directory of input files
+        #[arg(long, value_name = "DIR")]
+        inputs: PathBuf,
+    },
+    /// Re-run a day's tests and solution whenever its sources or inputs change
+    Watch {
+        /// Puzzle year
+        year: u16,
+        /// Puzzle day
+        day: u8,
+    },
+    /// Print a random valid input, e.g. `aoc generate 2022 8 --size 1000 | aoc 2022 8 -i -`
+    Generate {
+        /// Puzzle year
+        year: u16,
+        /// Puzzle day
+        day: u8,
+        /// Seed for the generator; the same seed gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Roughly how many records (lines, elves, moves, ...) to generate
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+    },
+}
+
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    init_tracing(cli.verbose, cli.run.explain);
+    let input_dir = env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+
+    let ok = match cli.command {
+        None => run(&cli.run, input_dir)?,
+        Some(Command::Verify { year, day, timeout }) => verify((year, day), timeout, input_dir)?,
+        Some(Command::Fetch { year, day }) => {
+            let config = Config::load()?;
+            let fetched = fetch::fetch(&config, &Ureq::default(), year, day)?;
+            match fetched {
+                Fetched::Downloaded(_) => eprintln!("downloaded {year} day {day}"),
+                Fetched::Cached(_) => eprintln!("{year} day {day} is already cached"),
+            }
+            println!("{}", fetched.path().display());
+            true
+        }
+        Some(Command::New { year, day }) => {
+            for path in scaffold::new_day(&input::workspace_root(), year, day)? {
+                println!("created {}", path.display());
+            }
+            true
+        }
+        Some(Command::Submit {
+            year,
+            day,
+            part,
+            input,
+        }) => {
+            let part = Part::try_from(part).expect("validated by clap");
+            submit((year, day, part), input.as_deref(), input_dir)?
+        }
+        Some(Command::Batch {
+            year,
+            day,
+            part,
+            inputs,
+        }) => batch((year, day, part), &inputs)?,
+        Some(Command::Watch { year, day }) => {
+            let day = days::find(year, day)
+                .with_context(|| format!("no solution for {year} day {day}"))?;
+            watch::watch(&input::workspace_root(), day)?;
+            true
+        }
+        Some(Command::Generate {
+            year,
+            day,
+            seed,
+            size,
+        }) => {
+            let input = generators::generate(year, day, seed, size)
+                .with_context(|| format!("no generator for {year} day {day}"))?;
+            print!("{input}");
+            true
+        }
+    };
+
+    Ok(if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn init_tracing(verbose: u8, explain: bool) {
+    let narrator = explain.then(|| Narrator::new(io::stdout()).with_filter(Narrator::filter()));
+
+    tracing_subscriber::registry()
+        .with(log::layer(verbose, io::stderr))
+        .with(narrator)
+        .init();
+}
+
+fn run(cli: &Run, input_dir: Option<PathBuf>) -> Result<bool> {
+    let selected = select(cli.year, cli.day)?;
+    let parts = match cli.part {
+        Some(part) => vec![Part::try_from(part).expect("validated by clap")],
+        None => Part::BOTH.to_vec(),
+    };
+    if let Some(path) = &cli.record {
+        recording_format(path)?;
+        visualize::start();
+    }
+
+    let timeout = (!cli.timeout.is_zero()).then_some(cli.timeout);
+
+    let mut failures = Vec::new();
+    let mut summary = Summary::default();
+    // Set once a timed-out thread is left running, since it skews every
+    // timing and memory count taken after it started.
+    let mut abandoned = false;
+    for &day in &selected {
+        let _day = info_span!("day", year = day.year, day = day.day).entered();
+        let skewed_day = abandoned;
+        let mut skewed_parts = BTreeSet::new();
+        let mut timings = Timings::default();
+        let (mut parse_usage, mut solve_usage) = (None, BTreeMap::new());
+        let source = Source::resolve(day, cli.input.as_deref(), input_dir.as_deref());
+        let (input, elapsed) = timing::time(|| read(day, &source));
+        timings.read = Some(elapsed);
+        let input_sha256 = input.as_deref().map(report::input_sha256);
+
+        let mut results = Vec::new();
+        if let Some(input) = input {
+            // A phase that times out keeps running on its thread, borrowing
+            // the input until the process exits.
+            let input: &'static str = input.leak();
+            let (parsed, figures) =
+                isolated(info_span!("parse"), timeout, &mut abandoned, move || {
+                    day.solution.parse(input)
+                });
+            if let Some((elapsed, usage)) = figures {
+                timings.parse = Some(elapsed);
+                parse_usage = Some(usage);
+            }
+            let parsed = match parsed {
+                Ok(Ok(parsed)) => Some(parsed),
+                Ok(Err(error)) => {
+                    eprintln!(
+                        "error: {} day {}: failed to parse {source}\n{}",
+                        day.year,
+                        day.day,
+                        error.render()
+                    );
+                    None
+                }
+                Err(error) => {
+                    eprintln!(
+                        "error: {} day {}: parsing {source} {error}",
+                        day.year, day.day
+                    );
+                    None
+                }
+            };
+
+            if let Some(parsed) = parsed {
+                let parsed: Arc<dyn Parsed> = Arc::from(parsed);
+                for &part in &parts {
+                    let label = format!("{} day {} part {}:", day.year, day.day, part);
+                    if abandoned {
+                        skewed_parts.insert(part);
+                    }
+                    let solver = Arc::clone(&parsed);
+                    let (answer, figures) = isolated(
+                        info_span!("part", part = part.number()),
+                        timeout,
+                        &mut abandoned,
+                        move || solver.solve(part),
+                    );
+                    if let Some((elapsed, usage)) = figures {
+                        *timings.solve_mut(part) = Some(elapsed);
+                        solve_usage.insert(part, usage);
+                    }
+                    let answer =
+                        answer.and_then(|answer| answer.map_err(|error| error.to_string()));
+                    match &answer {
+                        Ok(_) if cli.format == Format::Json => {}
+                        Ok(image @ Answer::Image(_)) => println!("{label}\n{image}"),
+                        Ok(answer) => println!("{label} {answer}"),
+                        Err(error) => eprintln!("error: {label} {error}"),
+                    }
+                    results.push((part, answer));
+                }
+            } else {
+                results.extend(
+                    parts
+                        .iter()
+                        .map(|&part| (part, Err("failed to parse the input".to_string()))),
+                );
+            }
+        } else {
+            results.extend(
+                parts
+                    .iter()
+                    .map(|&part| (part, Err("failed to read the input".to_string()))),
+            );
+        }
+
+        for (part, result) in &results {
+            if let Err(error) = result {
+                failures.push(format!("{} day {} part {part}: {error}", day.year, day.day));
+            }
+        }
+
+        match cli.format {
+            Format::Text => {
+                let note = unreliable(skewed_day, &skewed_parts);
+                println!("{} day {}: {}{note}", day.year, day.day, timings.describe());
+                if let (true, Some(parse_usage)) = (cli.memory, parse_usage) {
+                    let phases = iter::once(("parse".to_string(), parse_usage)).chain(
+                        solve_usage
+                            .iter()
+                            .map(|(part, &usage)| (format!("part {part}"), usage)),
+                    );
+                    let phases: Vec<String> = phases
+                        .map(|(phase, usage)| format!("{phase} {usage}"))
+                        .collect();
+                    println!(
+                        "{} day {} memory: {}{note}",
+                        day.year,
+                        day.day,
+                        phases.join("; ")
+                    );
+                }
+                summary.push(format!("{} day {}", day.year, day.day), timings);
+            }
+            Format::Json => {
+                for (part, result) in &results {
+                    let mut record =
+                        Record::new(day, *part, result, &timings, input_sha256.as_deref());
+                    if cli.memory {
+                        record.memory = Some(PartMemory {
+                            parse: parse_usage,
+                            solve: solve_usage.get(part).copied(),
+                        });
+                    }
+                    record.unreliable = skewed_day || skewed_parts.contains(part);
+                    println!("{}", record.to_json());
+                }
+            }
+        }
+    }
+
+    if selected.len() > 1 && cli.format == Format::Text {
+        print!("\n{}", summary.render());
+        if abandoned {
+            println!(
+                "note: figures measured after the first timeout are unreliable, as its thread is still running"
+            );
+        }
+        if !failures.is_empty() {
+            println!("\nfailed:");
+            for failure in &failures {
+                println!("  {failure}");
+            }
+        }
+    }
+    if let Some(path) = &cli.record {
+        save_recording(path, &visualize::finish(), usize::from(cli.scale))?;
+    }
+
+    Ok(failures.is_empty())
+}
+
+/// A `--timeout` in seconds, fractions allowed.
+fn seconds(value: &str) -> Result<Duration, String> {
+    let seconds: f64 = value.parse().map_err(|error| format!("{error}"))?;
+    Duration::try_from_secs_f64(seconds).map_err(|error| format!("{error}"))
+}
+
+/// The extension of a recording's path, if it is one we can write.
+fn recording_format(path: &Path) -> Result<&str> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension @ ("gif" | "png")) => Ok(extension),
+        _ => bail!(
+            "cannot record to {}, use a .gif or .png path",
+            path.display()
+        ),
+    }
+}
+
+fn save_recording(path: &Path, frames: &[visualize::Frame], scale: usize) -> Result<()> {
+    let Some(last) = frames.last() else {
+        bail!("the solution drew nothing to record");
+    };
+    let file = BufWriter::new(
+        File::create(path).with_context(|| format!("failed to create {}", path.display()))?,
+    );
+    match recording_format(path)? {
+        "gif" => visualize::gif(frames, scale, FRAME_DELAY, file),
+        _ => visualize::png(last, scale, file),
+    }
+    .with_context(|| format!("failed to write {}", path.display()))?;
+
+    eprintln!("recorded {} frames to {}", frames.len(), path.display());
+    Ok(())
+}
+
+/// Solves every selected day from its real input and compares with the
+/// registry. Unknown answers are reported but do not fail the run.
+fn verify(
+    (year, day): (Option<u16>, Option<u8>),
+    timeout: Duration,
+    input_dir: Option<PathBuf>,
+) -> Result<bool> {
+    let selected = select(year, day)?;
+    let answers = Answers::load(&input::workspace_root().join(ANSWERS_FILE))?;
+
+    let timeout = (!timeout.is_zero()).then_some(timeout);
+    // Verify reports no figures, so a thread left running does not matter.
+    let mut abandoned = false;
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+    for day in selected {
+        let source = Source::resolve(day, None, input_dir.as_deref());
+        let Some(input) = read(day, &source) else {
+            failed += Part::BOTH.len();
+            continue;
+        };
+        let input_sha256 = report::input_sha256(&input);
+        let input: &'static str = input.leak();
+        let (parsed, _) = isolated(info_span!("parse"), timeout, &mut abandoned, move || {
+            day.solution.parse(input)
+        });
+        let parsed: Arc<dyn Parsed> = match parsed {
+            Ok(Ok(parsed)) => Arc::from(parsed),
+            Ok(Err(error)) => {
+                eprintln!(
+                    "error: {} day {}: failed to parse {source}\n{}",
+                    day.year,
+                    day.day,
+                    error.render()
+                );
+                failed += Part::BOTH.len();
+                continue;
+            }
+            Err(error) => {
+                eprintln!(
+                    "error: {} day {}: parsing {source} {error}",
+                    day.year, day.day
+                );
+                failed += Part::BOTH.len();
+                continue;
+            }
+        };
+
+        for part in Part::BOTH {
+            let label = format!("{} day {} part {}:", day.year, day.day, part);
+            let solver = Arc::clone(&parsed);
+            let (answer, _) = isolated(
+                info_span!("part", part = part.number()),
+                timeout,
+                &mut abandoned,
+                move || solver.solve(part),
+            );
+            let answer = match answer.and_then(|answer| answer.map_err(|error| error.to_string())) {
+                Ok(answer) => answer.to_string(),
+                Err(error) => {
+                    eprintln!("error: {label} {error}");
+                    failed += 1;
+                    continue;
+                }
+            };
+
+            match answers.check(day.year, day.day, &input_sha256, part, &answer) {
+                Verdict::Pass => {
+                    println!("{label} pass");
+                    passed += 1;
+                }
+                Verdict::Fail { expected } => {
+                    println!("{label} FAIL, expected {expected:?}, got {answer:?}");
+                    failed += 1;
+                }
+                Verdict::Unknown => {
+                    println!("{label} unknown, got {answer:?}");
+                    unknown += 1;
+                }
+            }
+        }
+    }
+
+    println!("{passed} passed, {failed} failed, {unknown} unknown");
+    Ok(failed == 0)
+}
+
+fn submit(
+    (year, day, part): (u16, u8, Part),
+    path: Option<&Path>,
+    input_dir: Option<PathBuf>,
+) -> Result<bool> {
+    let day = days::find(year, day).with_context(|| format!("no solution for {year} day {day}"))?;
+    let source = Source::resolve(day, path, input_dir.as_deref());
+    let input = source.read()?;
+    let parsed = day
+        .solution
+        .parse(&input)
+        .map_err(|error| anyhow!("failed to parse {source}\n{}", error.render()))?;
+    let answer = parsed.solve(part)?;
+
+    let config = Config::load()?;
+    let label = format!("{year} day {} part {part}:", day.day);
+    let answer = answer.to_string();
+    match submit::submit(&config, &Ureq::default(), (year, day.day, part), &answer)? {
+        Submitted::AlreadyCorrect => {
+            println!("{label} {answer} (already accepted)");
+            Ok(true)
+        }
+        Submitted::Sent(outcome) => {
+            println!("{label} {answer} ({outcome})");
+            Ok(outcome == Outcome::Correct)
+        }
+    }
+}
+
+fn batch((year, day, part): (u16, u8, Option<u8>), inputs: &Path) -> Result<bool> {
+    let day = days::find(year, day).with_context(|| format!("no solution for {year} day {day}"))?;
+    let parts = match part {
+        Some(part) => vec![Part::try_from(part).expect("validated by clap")],
+        None => Part::BOTH.to_vec(),
+    };
+
+    let report = batch::run(day, inputs, &parts)?;
+    print!("{}", report.csv());
+    for failure in &report.failures {
+        eprintln!("failed: {}: {}", failure.file.display(), failure.reason);
+    }
+    Ok(report.failures.is_empty())
+}
+
+fn select(year: Option<u16>, day: Option<u8>) -> Result<Vec<&'static Day>> {
+    let selected: Vec<&Day> = match (year, day) {
+        (Some(year), Some(day)) => {
+            vec![days::find(year, day)
+                .with_context(|| format!("no solution for {year} day {day}"))?]
+        }
+        (Some(year), None) => DAYS.iter().filter(|d| d.year == year).collect(),
+        (None, _) => DAYS.iter().collect(),
+    };
+    if selected.is_empty() {
+        bail!("no solutions for {}", year.unwrap_or_default());
+    }
+    Ok(selected)
+}
+
+fn read(day: &Day, source: &Source) -> Option<String> {
+    source
+        .read()
+        .inspect_err(|error| eprintln!("error: {} day {}: {error:#}", day.year, day.day))
+        .ok()
+}
+
+/// Runs a phase on a thread of its own, so that a panic or a hang cannot take
+/// the runner down, with its time and allocations if it finished. Sets
+/// `abandoned` when the thread has to be left running.
+fn isolated<T: Send + 'static>(
+    span: Span,
+    timeout: Option<Duration>,
+    abandoned: &mut bool,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> (Result<T, String>, Option<(Duration, Usage)>) {
+    let outcome = isolate::run(timeout, {
+        let span = span.clone();
+        move || span.in_scope(|| memory::measure(|| timing::time(f)))
+    });
+    match outcome {
+        isolate::Outcome::Done(((output, elapsed), usage)) => {
+            span.in_scope(|| info!(?elapsed, "finished"));
+            (Ok(output), Some((elapsed, usage)))
+        }
+        isolate::Outcome::Panicked(message) => (Err(format!("panicked: {message}")), None),
+        isolate::Outcome::TimedOut => {
+            *abandoned = true;
+            let timeout = timeout.expect("only a limited phase times out");
+            (Err(format!("timed out after {timeout:?}")), None)
+        }
+    }
+}
+
+/// Notes which of a day's figures were measured while a timed-out thread was
+/// still running, if any.
+fn unreliable(skewed_day: bool, skewed_parts: &BTreeSet<Part>) -> String {
+    let figures = if skewed_day {
+        String::new()
+    } else if let Some(part) = skewed_parts.first() {
+        format!("part {part} ")
+    } else {
+        return String::new();
+    };
+    format!(" ({figures}unreliable: a timed-out thread is still running)")
+}
@@ -0,0 +1,156 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::{self, Display},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use serde::Serialize;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator, counting what passes through it. Install it with
+/// `#[global_allocator]` for [`measure`] to see anything.
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grew(0, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grew(0, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            grew(layout.size(), new_size);
+        }
+        new
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+}
+
+/// Counts a block of `old` bytes becoming one of `new`, `old` being 0 for a
+/// fresh allocation.
+fn grew(old: usize, new: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(new as u64, Ordering::Relaxed);
+    let live = if new >= old {
+        LIVE.fetch_add((new - old) as u64, Ordering::Relaxed) + (new - old) as u64
+    } else {
+        LIVE.fetch_sub((old - new) as u64, Ordering::Relaxed) - (old - new) as u64
+    };
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+/// What one phase asked of the allocator. A reallocation counts as an
+/// allocation of its new size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Usage {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Most memory live at once, above what was live when the phase began.
+    pub peak_bytes: u64,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} (peak {})",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// A byte count in the largest binary unit that keeps it at least 1.
+struct Bytes(u64);
+
+impl Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+        let (mut size, mut unit) = (self.0 as f64, 0);
+        while size >= 1024.0 && unit + 1 < UNITS.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        match unit {
+            0 => write!(f, "{} B", self.0),
+            _ => write!(f, "{size:.1} {}", UNITS[unit]),
+        }
+    }
+}
+
+/// Runs `f` and counts its allocations. Allocations made meanwhile by other
+/// threads are counted too, so measure one thing at a time.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    let (allocations, bytes) = (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        BYTES.load(Ordering::Relaxed),
+    );
+
+    let output = f();
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (output, usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn test_measure() {
+        let (sum, usage) = measure(|| {
+            let mut numbers: Vec<u64> = Vec::with_capacity(1000);
+            numbers.extend(0..1000);
+            let total: u64 = numbers.iter().sum();
+            drop(numbers);
+            vec![total; 100][0]
+        });
+
+        // Other tests allocate at the same time, so only lower bounds hold.
+        assert_eq!(sum, 499500);
+        assert!(usage.allocations >= 2, "{usage:?}");
+        assert!(usage.bytes >= 8800, "{usage:?}");
+        assert!(usage.peak_bytes >= 8000, "{usage:?}");
+    }
+
+    #[test]
+    fn test_display() {
+        let usage = Usage {
+            allocations: 3,
+            bytes: 1536,
+            peak_bytes: 1000,
+        };
+        assert_eq!(usage.to_string(), "3 allocations, 1.5 KiB (peak 1000 B)");
+        assert_eq!(Bytes(5 * 1024 * 1024).to_string(), "5.0 MiB");
+    }
+}
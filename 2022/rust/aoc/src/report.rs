@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use aoc_core::{Answer, Part};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{days::Day, memory::Usage, timing::Timings};
+
+/// One part's result, printed as a line of JSON by `aoc --format json`.
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// `None` when the part could not be solved, see `error`.
+    pub answer: Option<&'a Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'a str>,
+    pub timings: PartTimings,
+    /// What parsing and solving the part allocated, when asked for with
+    /// `--memory`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<PartMemory>,
+    /// Tells results for different inputs apart; `None` if it could not be read.
+    pub input_sha256: Option<&'a str>,
+    /// Set once an earlier phase timed out, as its thread still running skews
+    /// `timings` and `memory`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub unreliable: bool,
+}
+
+/// The phases behind one part, in nanoseconds.
+#[derive(Debug, Serialize)]
+pub struct PartTimings {
+    pub read_ns: Option<u64>,
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+}
+
+/// The allocations behind one part, `None` for a phase that did not finish.
+#[derive(Debug, Serialize)]
+pub struct PartMemory {
+    pub parse: Option<Usage>,
+    pub solve: Option<Usage>,
+}
+
+impl<'a> Record<'a> {
+    pub fn new(
+        day: &Day,
+        part: Part,
+        result: &'a Result<Answer, String>,
+        timings: &Timings,
+        input_sha256: Option<&'a str>,
+    ) -> Self {
+        let nanos = |time: Option<Duration>| time.map(|time| time.as_nanos() as u64);
+
+        Record {
+            year: day.year,
+            day: day.day,
+            part: part.number(),
+            answer: result.as_ref().ok(),
+            error: result.as_ref().err().map(String::as_str),
+            timings: PartTimings {
+                read_ns: nanos(timings.read),
+                parse_ns: nanos(timings.parse),
+                solve_ns: nanos(timings.solve(part)),
+            },
+            memory: None,
+            input_sha256,
+            unreliable: false,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("records always serialize")
+    }
+}
+
+/// Hex SHA-256 of a puzzle input.
+pub fn input_sha256(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_input_sha256() {
+        assert_eq!(
+            input_sha256("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_record_json() {
+        let day = days::find(2022, 1).unwrap();
+        let timings = Timings {
+            read: Some(Duration::from_nanos(10)),
+            parse: Some(Duration::from_nanos(20)),
+            solve: [Some(Duration::from_nanos(30)), None],
+        };
+
+        let solved = Ok(Answer::Integer(24000));
+        assert_eq!(
+            Record::new(day, Part::One, &solved, &timings, Some("ab12")).to_json(),
+            r#"{"year":2022,"day":1,"part":1,"answer":{"type":"integer","value":24000},"timings":{"read_ns":10,"parse_ns":20,"solve_ns":30},"input_sha256":"ab12"}"#
+        );
+
+        let failed = Err("there are no elves".to_string());
+        assert_eq!(
+            Record::new(day, Part::Two, &failed, &timings, None).to_json(),
+            r#"{"year":2022,"day":1,"part":2,"answer":null,"error":"there are no elves","timings":{"read_ns":10,"parse_ns":20,"solve_ns":null},"input_sha256":null}"#
+        );
+
+        let mut record = Record::new(day, Part::One, &solved, &timings, None);
+        record.memory = Some(PartMemory {
+            parse: Some(Usage {
+                allocations: 2,
+                bytes: 64,
+                peak_bytes: 48,
+            }),
+            solve: None,
+        });
+        assert!(record.to_json().contains(
+            r#""memory":{"parse":{"allocations":2,"bytes":64,"peak_bytes":48},"solve":null}"#
+        ));
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+/// Every day crate of this workspace belongs to this year, so crates are
+/// named after the day alone.
+pub const WORKSPACE_YEAR: u16 = 2022;
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml.template");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.template");
+
+/// Creates `day-<day>` from the template under the workspace `root`, with
+/// placeholder `input.txt` and `test.txt` files, and registers it in the
+/// workspace and the runner. Returns the files written.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>> {
+    if year != WORKSPACE_YEAR {
+        bail!("this workspace holds {WORKSPACE_YEAR} only");
+    }
+    let name = format!("day-{day}");
+    let dir = root.join(&name);
+    if dir.exists() {
+        bail!("{year} day {day} already exists in {}", dir.display());
+    }
+
+    // Work out every edit before writing anything, so a failure leaves the
+    // workspace untouched.
+    let workspace = root.join("Cargo.toml");
+    let runner = root.join("aoc").join("Cargo.toml");
+    let days_rs = root.join("aoc").join("src").join("days.rs");
+
+    let workspace_text = insert_line(
+        &read(&workspace)?,
+        day,
+        format!("    \"{name}\","),
+        |line| {
+            line.trim()
+                .strip_prefix("\"day-")?
+                .strip_suffix("\",")?
+                .parse()
+                .ok()
+        },
+    )
+    .with_context(|| format!("failed to register {name} in {}", workspace.display()))?;
+
+    let runner_text = insert_line(
+        &read(&runner)?,
+        day,
+        format!("{name} = {{ path = \"../{name}\" }}"),
+        |line| line.strip_prefix("day-")?.split_once(' ')?.0.parse().ok(),
+    )
+    .with_context(|| format!("failed to register {name} in {}", runner.display()))?;
+
+    let days_text = insert_line(
+        &read(&days_rs)?,
+        day,
+        format!("    day({year}, {day}, \"{name}/input.txt\", &day_{day}::Day{day}),"),
+        |line| {
+            let (line_year, rest) = line.trim().strip_prefix("day(")?.split_once(", ")?;
+            (line_year.parse() == Ok(year)).then_some(())?;
+            rest.split_once(',')?.0.parse().ok()
+        },
+    )
+    .with_context(|| format!("failed to register {name} in {}", days_rs.display()))?;
+
+    let render = |template: &str| {
+        template
+            .replace("day-N", &name)
+            .replace("DayN", &format!("Day{day}"))
+    };
+    let files = [
+        (dir.join("Cargo.toml"), render(CARGO_TEMPLATE)),
+        (dir.join("src").join("lib.rs"), render(LIB_TEMPLATE)),
+        (dir.join("input.txt"), String::new()),
+        (dir.join("test.txt"), String::new()),
+    ];
+    for (path, contents) in &files {
+        let parent = path.parent().expect("day files live in a directory");
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+        write(path, contents)?;
+    }
+
+    write(&workspace, &workspace_text)?;
+    write(&runner, &runner_text)?;
+    write(&days_rs, &days_text)?;
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+/// Inserts `line` among the lines that `day_of` recognises, keeping them in
+/// day order.
+fn insert_line(
+    text: &str,
+    day: u8,
+    line: String,
+    day_of: impl Fn(&str) -> Option<u8>,
+) -> Result<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let days: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| Some((idx, day_of(line)?)))
+        .collect();
+
+    if days.iter().any(|&(_, existing)| existing == day) {
+        bail!("day {day} is already listed");
+    }
+    let idx = match days.iter().rfind(|&&(_, existing)| existing < day) {
+        Some(&(idx, _)) => idx + 1,
+        None => match days.first() {
+            Some(&(idx, _)) => idx,
+            None => bail!("found no other day to place it next to"),
+        },
+    };
+
+    lines.insert(idx, &line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        let path = |rel: &str| root.path().join(rel);
+
+        fs::create_dir_all(path("aoc/src")).unwrap();
+        fs::create_dir_all(path("day-11")).unwrap();
+        fs::write(
+            path("Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day-9\",\n    \"day-11\",\n    \"grid\",\n]\n",
+        )
+        .unwrap();
+        fs::write(
+            path("aoc/Cargo.toml"),
+            "[dependencies]\nday-9 = { path = \"../day-9\" }\nday-11 = { path = \"../day-11\" }\nserde = \"1\"\n",
+        )
+        .unwrap();
+        fs::write(
+            path("aoc/src/days.rs"),
+            "pub const DAYS: &[Day] = &[\n    day(2022, 11, \"day-11/input.txt\", &day_11::Day11),\n];\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn test_new_day_is_registered_in_order() {
+        let root = workspace();
+        let files = new_day(root.path(), 2022, 12).unwrap();
+
+        assert_eq!(files.len(), 4);
+        let lib = fs::read_to_string(root.path().join("day-12/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day12;"));
+        assert!(lib.contains("include_str!(\"../test.txt\")"));
+        assert_eq!(
+            fs::read_to_string(root.path().join("Cargo.toml")).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day-9\",\n    \"day-11\",\n    \"day-12\",\n    \"grid\",\n]\n"
+        );
+        assert!(fs::read_to_string(root.path().join("aoc/Cargo.toml"))
+            .unwrap()
+            .contains(
+                "day-11 = { path = \"../day-11\" }\nday-12 = { path = \"../day-12\" }\nserde"
+            ));
+        assert!(fs::read_to_string(root.path().join("aoc/src/days.rs"))
+            .unwrap()
+            .contains(
+                "&day_11::Day11),\n    day(2022, 12, \"day-12/input.txt\", &day_12::Day12),\n];"
+            ));
+    }
+
+    #[test]
+    fn test_existing_day_is_not_overwritten() {
+        let root = workspace();
+        fs::write(root.path().join("day-11/input.txt"), "mine").unwrap();
+
+        let error = new_day(root.path(), 2022, 11).unwrap_err();
+        assert!(error.to_string().starts_with("2022 day 11 already exists"));
+        assert_eq!(
+            fs::read_to_string(root.path().join("day-11/input.txt")).unwrap(),
+            "mine"
+        );
+
+        // A registration without a crate directory is refused before anything is written.
+        let error = new_day(root.path(), 2022, 9).unwrap_err();
+        assert!(format!("{error:#}").contains("day 9 is already listed"));
+        assert!(!root.path().join("day-9").exists());
+    }
+}
@@ -0,0 +1,347 @@
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use aoc_core::Part;
+use serde::{Deserialize, Serialize};
+
+use crate::{config::Config, fetch::throttled, http::Transport};
+
+/// What the site made of a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way.
+    Incorrect,
+    /// Submitted too soon after the previous answer; nothing was checked.
+    #[serde(skip)]
+    Wait(Duration),
+    /// The part is locked or already solved; nothing was checked.
+    #[serde(skip)]
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Reads the outcome from the page the site answers a submission with.
+    pub fn parse(page: &str) -> Result<Self> {
+        let text = page_text(page).to_lowercase();
+
+        if text.contains("that's the right answer") {
+            Ok(Outcome::Correct)
+        } else if text.contains("your answer is too high") {
+            Ok(Outcome::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Ok(Outcome::TooLow)
+        } else if text.contains("that's not the right answer") {
+            Ok(Outcome::Incorrect)
+        } else if let Some(wait) = wait_time(&text) {
+            Ok(Outcome::Wait(wait))
+        } else if text.contains("you don't seem to be solving the right level") {
+            Ok(Outcome::WrongLevel)
+        } else {
+            bail!("unrecognised response: {}", text.trim())
+        }
+    }
+
+    /// Whether the site actually checked the answer.
+    pub fn is_verdict(self) -> bool {
+        !matches!(self, Outcome::Wait(_) | Outcome::WrongLevel)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => f.write_str("correct"),
+            Outcome::TooHigh => f.write_str("too high"),
+            Outcome::TooLow => f.write_str("too low"),
+            Outcome::Incorrect => f.write_str("incorrect"),
+            Outcome::Wait(wait) => match wait.as_secs().div_ceil(60) {
+                1 => f.write_str("wait 1 minute"),
+                minutes => write!(f, "wait {minutes} minutes"),
+            },
+            Outcome::WrongLevel => f.write_str("wrong level, is the part already solved?"),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, with tags removed.
+fn page_text(page: &str) -> String {
+    // Inside an article, start within the rest of its opening tag.
+    let (article, mut in_tag) = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or((page, false), |(article, _)| (article, true));
+
+    let mut text = String::new();
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Parses `you have 4m 23s left to wait`.
+fn wait_time(text: &str) -> Option<Duration> {
+    let (before, _) = text.split_once(" left to wait")?;
+    let (_, time) = before.rsplit_once("you have ")?;
+
+    time.split_whitespace()
+        .map(|part| {
+            let units = [("h", 3600), ("m", 60), ("s", 1)];
+            let (value, seconds) = units
+                .iter()
+                .find_map(|&(unit, seconds)| Some((part.strip_suffix(unit)?, seconds)))?;
+            let value: u64 = value.parse().ok()?;
+            Some(value * seconds)
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Every checked answer of one year, kept so no wrong answer is sent twice.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "submission")]
+    entries: Vec<Entry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Entry {
+    day: u8,
+    part: u8,
+    answer: String,
+    outcome: Outcome,
+}
+
+/// Why an answer does not need to be sent.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Known {
+    Accepted(String),
+    /// Ruled out by `answer`, either the same one or a bound it set.
+    Rejected {
+        answer: String,
+        outcome: Outcome,
+    },
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(History::default());
+        }
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&source).with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn record(&mut self, day: u8, part: Part, answer: &str, outcome: Outcome) {
+        self.entries.push(Entry {
+            day,
+            part: part.number(),
+            answer: answer.to_string(),
+            outcome,
+        });
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Option<Known> {
+        let entries = self
+            .entries
+            .iter()
+            .filter(|e| e.day == day && e.part == part.number());
+        let number = answer.parse::<i128>().ok();
+
+        for entry in entries {
+            if entry.outcome == Outcome::Correct {
+                return Some(Known::Accepted(entry.answer.clone()));
+            }
+
+            let earlier = entry.answer.parse::<i128>().ok();
+            let ruled_out = entry.answer == answer
+                || match (entry.outcome, number, earlier) {
+                    (Outcome::TooHigh, Some(number), Some(high)) => number >= high,
+                    (Outcome::TooLow, Some(number), Some(low)) => number <= low,
+                    _ => false,
+                };
+            if ruled_out {
+                return Some(Known::Rejected {
+                    answer: entry.answer.clone(),
+                    outcome: entry.outcome,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// `<cache_dir>/<year>/submissions.toml`
+pub fn history_path(cache_dir: &Path, year: u16) -> PathBuf {
+    cache_dir.join(year.to_string()).join("submissions.toml")
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Submitted {
+    Sent(Outcome),
+    /// The same answer was accepted before, so nothing was sent.
+    AlreadyCorrect,
+}
+
+/// Posts `answer` unless the history already rules on it, and records what
+/// the site says.
+pub fn submit(
+    config: &Config,
+    transport: &dyn Transport,
+    (year, day, part): (u16, u8, Part),
+    answer: &str,
+) -> Result<Submitted> {
+    if answer.contains('\n') {
+        bail!("multi-line answers have to be read off and submitted by hand");
+    }
+
+    let cache_dir = config.cache_dir()?;
+    let path = history_path(&cache_dir, year);
+    let mut history = History::load(&path)?;
+
+    match history.check(day, part, answer) {
+        Some(Known::Accepted(accepted)) if accepted == answer => {
+            return Ok(Submitted::AlreadyCorrect)
+        }
+        Some(Known::Accepted(accepted)) => {
+            bail!("{year} day {day} part {part} was already solved with {accepted}")
+        }
+        Some(Known::Rejected {
+            answer: earlier,
+            outcome,
+        }) if earlier == answer => bail!("{answer} was already submitted and was {outcome}"),
+        Some(Known::Rejected {
+            answer: earlier,
+            outcome,
+        }) => bail!("{answer} cannot be right, {earlier} was already {outcome}"),
+        None => {}
+    }
+
+    let url = format!("{}/{year}/day/{day}/answer", config.base_url);
+    let level = part.to_string();
+    let form = [("level", level.as_str()), ("answer", answer)];
+    let response = throttled(config, &cache_dir, || {
+        transport.post(&url, &config.session, &form)
+    })?;
+    if response.status != 200 {
+        bail!(
+            "{url} answered with status {}, the session token may have expired",
+            response.status
+        );
+    }
+
+    let outcome = Outcome::parse(&response.body)?;
+    if outcome.is_verdict() {
+        history.record(day, part, answer, outcome);
+        history.save(&path)?;
+    }
+    Ok(Submitted::Sent(outcome))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{mock::Server, Ureq};
+
+    const TOO_HIGH: &str = r#"<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href="/2022/day/9">[Return to Day 9]</a></p></article></main>"#;
+    const CORRECT: &str = r#"<main><article><p>That's the right answer!  You are <span class="day-success">one gold star</span> closer to collecting enough star fruit.</p></article></main>"#;
+
+    #[test]
+    fn test_parse_outcome() {
+        let page =
+            |text: &str| format!("<html><main><article><p>{text}</p></article></main></html>");
+
+        assert_eq!(Outcome::parse(CORRECT).unwrap(), Outcome::Correct);
+        assert_eq!(Outcome::parse(TOO_HIGH).unwrap(), Outcome::TooHigh);
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's not the right answer; your answer is too low."
+            ))
+            .unwrap(),
+            Outcome::TooLow
+        );
+        let wait = Outcome::parse(&page(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 23s left to wait.",
+        ))
+        .unwrap();
+        assert_eq!(wait, Outcome::Wait(Duration::from_secs(263)));
+        assert_eq!(wait.to_string(), "wait 5 minutes");
+        assert_eq!(
+            Outcome::parse(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+            .unwrap(),
+            Outcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn test_wait_time() {
+        assert_eq!(
+            wait_time("you have 1h 2m 3s left to wait"),
+            Some(Duration::from_secs(3723))
+        );
+        assert_eq!(wait_time("you have 4µ left to wait"), None);
+        assert_eq!(wait_time("you have é left to wait"), None);
+    }
+
+    #[test]
+    fn test_wrong_answers_are_never_resent() {
+        let server = Server::start(|request| {
+            if request.contains("answer=2566") {
+                (200, CORRECT.to_string())
+            } else {
+                (200, TOO_HIGH.to_string())
+            }
+        });
+        let cache = tempfile::tempdir().unwrap();
+        let config = Config {
+            session: "53616c7465".to_string(),
+            base_url: server.url.clone(),
+            cache_dir: Some(cache.path().to_path_buf()),
+            request_interval_secs: 0.0,
+        };
+        let puzzle = (2022, 9, Part::Two);
+        let submit = |answer| submit(&config, &Ureq::default(), puzzle, answer);
+
+        assert_eq!(submit("3000").unwrap(), Submitted::Sent(Outcome::TooHigh));
+        assert_eq!(
+            submit("3000").unwrap_err().to_string(),
+            "3000 was already submitted and was too high"
+        );
+        assert_eq!(
+            submit("3100").unwrap_err().to_string(),
+            "3100 cannot be right, 3000 was already too high"
+        );
+        assert_eq!(submit("2566").unwrap(), Submitted::Sent(Outcome::Correct));
+        assert_eq!(submit("2566").unwrap(), Submitted::AlreadyCorrect);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("POST /2022/day/9/answer "));
+        assert!(requests[0].ends_with("level=2&answer=3000"));
+    }
+}
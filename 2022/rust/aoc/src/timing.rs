@@ -0,0 +1,182 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use aoc_core::Part;
+
+/// How long each phase of one day took. Phases that never ran are `None`.
+#[derive(Clone, Debug, Default)]
+pub struct Timings {
+    pub read: Option<Duration>,
+    pub parse: Option<Duration>,
+    pub solve: [Option<Duration>; 2],
+}
+
+impl Timings {
+    pub fn solve(&self, part: Part) -> Option<Duration> {
+        match part {
+            Part::One => self.solve[0],
+            Part::Two => self.solve[1],
+        }
+    }
+
+    pub fn solve_mut(&mut self, part: Part) -> &mut Option<Duration> {
+        match part {
+            Part::One => &mut self.solve[0],
+            Part::Two => &mut self.solve[1],
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        [self.read, self.parse]
+            .iter()
+            .chain(&self.solve)
+            .flatten()
+            .sum()
+    }
+
+    /// One line, e.g. `read 41.0µs, parse 310.2µs, part 1 2.1µs`.
+    pub fn describe(&self) -> String {
+        let phases = [
+            ("read".to_string(), self.read),
+            ("parse".to_string(), self.parse),
+        ];
+        let parts = Part::BOTH
+            .iter()
+            .zip(self.solve)
+            .map(|(part, time)| (format!("part {part}"), time));
+
+        phases
+            .into_iter()
+            .chain(parts)
+            .filter_map(|(phase, time)| Some(format!("{phase} {:.1?}", time?)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Runs `f` and measures its wall-clock time.
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let output = f();
+    (output, start.elapsed())
+}
+
+/// Timings of several days, rendered as a table with each day's share of the total.
+#[derive(Default)]
+pub struct Summary {
+    rows: Vec<(String, Timings)>,
+}
+
+impl Summary {
+    pub fn push(&mut self, label: String, timings: Timings) {
+        self.rows.push((label, timings));
+    }
+
+    pub fn render(&self) -> String {
+        let total: Duration = self.rows.iter().map(|(_, t)| t.total()).sum();
+        let cell = |time: Option<Duration>| time.map_or("-".to_string(), |t| format!("{t:.1?}"));
+
+        let mut rows =
+            vec![["day", "read", "parse", "part 1", "part 2", "total", "share"].map(String::from)];
+        for (label, timings) in &self.rows {
+            let share = if total.is_zero() {
+                0.0
+            } else {
+                timings.total().as_secs_f64() / total.as_secs_f64() * 100.0
+            };
+            rows.push([
+                label.clone(),
+                cell(timings.read),
+                cell(timings.parse),
+                cell(timings.solve[0]),
+                cell(timings.solve[1]),
+                cell(Some(timings.total())),
+                format!("{share:.1}%"),
+            ]);
+        }
+        rows.push([
+            "total".to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            cell(Some(total)),
+            "100.0%".to_string(),
+        ]);
+
+        let mut widths = [0; 7];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let mut table = String::new();
+        for row in &rows {
+            let mut line = format!("{:<1$}", row[0], widths[0]);
+            for (cell, width) in row.iter().zip(widths).skip(1) {
+                write!(line, "  {cell:>width$}").unwrap();
+            }
+            table.push_str(line.trim_end());
+            table.push('\n');
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(n: u64) -> Option<Duration> {
+        Some(Duration::from_micros(n))
+    }
+
+    #[test]
+    fn test_describe_skips_missing_phases() {
+        let timings = Timings {
+            read: micros(40),
+            parse: micros(300),
+            solve: [micros(5), None],
+        };
+
+        assert_eq!(timings.total(), Duration::from_micros(345));
+        assert_eq!(
+            timings.describe(),
+            "read 40.0µs, parse 300.0µs, part 1 5.0µs"
+        );
+    }
+
+    #[test]
+    fn test_summary_shares() {
+        let mut summary = Summary::default();
+        summary.push(
+            "2022 day 1".to_string(),
+            Timings {
+                read: micros(10),
+                parse: micros(20),
+                solve: [micros(30), micros(40)],
+            },
+        );
+        summary.push(
+            "2022 day 2".to_string(),
+            Timings {
+                read: micros(100),
+                parse: micros(100),
+                solve: [micros(100), micros(100)],
+            },
+        );
+
+        assert_eq!(
+            summary.render(),
+            "\
+day            read    parse   part 1   part 2    total   share
+2022 day 1   10.0µs   20.0µs   30.0µs   40.0µs  100.0µs   20.0%
+2022 day 2  100.0µs  100.0µs  100.0µs  100.0µs  400.0µs   80.0%
+total                                           500.0µs  100.0%
+"
+        );
+    }
+}
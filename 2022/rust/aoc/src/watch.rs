@@ -0,0 +1,223 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{bail, Context, Result};
+use aoc_core::{Answer, Part};
+use serde::Deserialize;
+
+use crate::{
+    answers::{Answers, Verdict, ANSWERS_FILE},
+    days::Day,
+};
+
+/// How often the day's files are checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification times of the files a day's answers depend on.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    /// Everything under the crate's `src/`, its manifest and its `.txt` inputs.
+    pub fn take(crate_dir: &Path) -> Snapshot {
+        let mut files = BTreeMap::new();
+        visit(&crate_dir.join("src"), &mut files);
+
+        for entry in fs::read_dir(crate_dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "txt") || path.ends_with("Cargo.toml") {
+                insert(path, &mut files);
+            }
+        }
+        Snapshot(files)
+    }
+}
+
+fn visit(dir: &Path, files: &mut BTreeMap<PathBuf, SystemTime>) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            visit(&path, files);
+        } else {
+            insert(path, files);
+        }
+    }
+}
+
+fn insert(path: PathBuf, files: &mut BTreeMap<PathBuf, SystemTime>) {
+    // A file removed mid-scan is simply missing from the snapshot.
+    if let Ok(modified) = fs::metadata(&path).and_then(|metadata| metadata.modified()) {
+        files.insert(path, modified);
+    }
+}
+
+/// The parts of a JSON record from `aoc --format json` that watch needs.
+#[derive(Debug, Deserialize)]
+struct Solved {
+    part: u8,
+    answer: Option<Answer>,
+    error: Option<String>,
+    input_sha256: Option<String>,
+}
+
+/// Re-runs the day's tests and solution whenever its files change, until killed.
+pub fn watch(root: &Path, day: &Day) -> Result<()> {
+    let crate_dir = root.join(day.dir());
+    if !crate_dir.is_dir() {
+        bail!("{} does not exist", crate_dir.display());
+    }
+
+    let mut last_answers: BTreeMap<Part, String> = BTreeMap::new();
+    let mut seen = Snapshot::default();
+    loop {
+        let snapshot = Snapshot::take(&crate_dir);
+        if snapshot == seen {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+
+        println!("--- {} day {} ---", day.year, day.day);
+        test(root, day)?;
+        for (part, answer, input_sha256) in solve(root, day)? {
+            let known = Answers::load(&root.join(ANSWERS_FILE))
+                .map(|answers| answers.check(day.year, day.day, &input_sha256, part, &answer))
+                .unwrap_or(Verdict::Unknown);
+            let change = describe_change(last_answers.get(&part).map(String::as_str), &answer);
+
+            match answer.contains('\n') {
+                true => println!("part {part}: ({change}, {})\n{answer}", describe(&known)),
+                false => println!("part {part}: {answer} ({change}, {})", describe(&known)),
+            }
+            last_answers.insert(part, answer);
+        }
+        // Building and running may have rewritten files, e.g. Cargo.lock.
+        seen = Snapshot::take(&crate_dir);
+    }
+}
+
+/// Runs the day crate's example tests, showing their output only if they fail.
+fn test(root: &Path, day: &Day) -> Result<()> {
+    let output = cargo(root, &["test", "-q", "-p", day.dir()])?;
+    if output.status.success() {
+        println!("tests: pass");
+    } else {
+        println!("tests: FAIL");
+        print!("{}", String::from_utf8_lossy(&output.stdout));
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+    Ok(())
+}
+
+/// Builds and runs the current solution on the real input, giving each
+/// answer with the input's SHA-256.
+fn solve(root: &Path, day: &Day) -> Result<Vec<(Part, String, String)>> {
+    let (year, number) = (day.year.to_string(), day.day.to_string());
+    let output = cargo(
+        root,
+        &[
+            "run",
+            "-q",
+            "--release",
+            "-p",
+            "aoc",
+            "--",
+            &year,
+            &number,
+            "--format",
+            "json",
+        ],
+    )?;
+    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+
+    let mut answers = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let solved: Solved = serde_json::from_str(line)
+            .with_context(|| format!("unexpected output from aoc: {line}"))?;
+        let part = Part::try_from(solved.part).map_err(|part| anyhow::anyhow!("no part {part}"))?;
+
+        match (solved.answer, solved.error) {
+            (Some(answer), _) => answers.push((
+                part,
+                answer.to_string(),
+                solved.input_sha256.unwrap_or_default(),
+            )),
+            (None, error) => println!("part {part}: error: {}", error.unwrap_or_default()),
+        }
+    }
+    Ok(answers)
+}
+
+fn cargo(root: &Path, args: &[&str]) -> Result<Output> {
+    Command::new(env!("CARGO"))
+        .args(args)
+        .current_dir(root)
+        .output()
+        .with_context(|| format!("failed to run cargo {}", args.join(" ")))
+}
+
+fn describe(known: &Verdict) -> String {
+    match known {
+        Verdict::Pass => "correct".to_string(),
+        Verdict::Fail { expected } if expected.contains('\n') => "WRONG".to_string(),
+        Verdict::Fail { expected } => format!("WRONG, expected {expected}"),
+        Verdict::Unknown => "no known answer".to_string(),
+    }
+}
+
+/// A short account of how an answer differs from the previous run's.
+fn describe_change(previous: Option<&str>, answer: &str) -> String {
+    match previous {
+        None => "first run".to_string(),
+        Some(previous) if previous == answer => "unchanged".to_string(),
+        Some(previous) if !previous.contains('\n') && !answer.contains('\n') => {
+            format!("was {previous}")
+        }
+        Some(previous) => {
+            let (before, after): (Vec<_>, Vec<_>) =
+                (previous.lines().collect(), answer.lines().collect());
+            let changed: Vec<String> = (0..before.len().max(after.len()))
+                .filter(|&row| before.get(row) != after.get(row))
+                .map(|row| (row + 1).to_string())
+                .collect();
+            format!("rows {} changed", changed.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_sees_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/bin")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+        fs::write(dir.path().join("input.txt"), "1").unwrap();
+        fs::write(dir.path().join("notes.md"), "").unwrap();
+
+        let before = Snapshot::take(dir.path());
+        assert_eq!(before.0.len(), 2);
+        assert_eq!(Snapshot::take(dir.path()), before);
+
+        fs::write(dir.path().join("src/bin/part_1.rs"), "").unwrap();
+        assert_ne!(Snapshot::take(dir.path()), before);
+    }
+
+    #[test]
+    fn test_describe_change() {
+        assert_eq!(describe_change(None, "13"), "first run");
+        assert_eq!(describe_change(Some("13"), "13"), "unchanged");
+        assert_eq!(describe_change(Some("13"), "36"), "was 13");
+        assert_eq!(
+            describe_change(Some("#.\n.#\n##"), "#.\n##\n#."),
+            "rows 2, 3 changed"
+        );
+    }
+}
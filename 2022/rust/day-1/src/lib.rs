@@ -1,28 +1,48 @@
-pub fn part_1(input: &str) -> u32 {
-    input
-        .split("\n\n")
-        .map(|item| {
-            item.split("\n")
-                .map(|c| c.parse::<u32>().unwrap())
-                .sum::<u32>()
-        })
-        .max()
-        .unwrap()
-}
+use aoc_core::{finish, Error, ParseError, Result, Solution, EXPLAIN};
+use parsers::{blocks, lines, number};
+use tracing::{debug, info};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input<'a> = Vec<u64>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+        let elves = finish(input, blocks(lines(number::<u32>))(input))?;
 
-pub fn part_2(input: &str) -> u32 {
-    let mut res = input
-        .split("\n\n")
-        .map(|item| {
-            item.split("\n")
-                .map(|c| c.parse::<u32>().unwrap())
-                .sum::<u32>()
-        })
-        .collect::<Vec<_>>();
+        info!(elves = elves.len(), "parsed snacks");
 
-    res.sort_by(|a, b| b.cmp(a));
+        // Summed in 64 bits, so a few large snacks cannot overflow.
+        Ok(elves
+            .iter()
+            .map(|snacks| snacks.iter().map(|&calories| u64::from(calories)).sum())
+            .collect())
+    }
+
+    fn part_1(elves: &Vec<u64>) -> Result<u64> {
+        let (elf, &calories) = elves
+            .iter()
+            .enumerate()
+            .max_by_key(|&(_, calories)| calories)
+            .ok_or_else(|| Error::solve("there are no elves"))?;
+        debug!(target: EXPLAIN, "elf {} carries the most, {calories} calories", elf + 1);
+
+        Ok(calories)
+    }
 
-    res.iter().take(3).sum()
+    fn part_2(elves: &Vec<u64>) -> Result<u64> {
+        let mut res: Vec<(usize, u64)> = elves.iter().copied().enumerate().collect();
+
+        res.sort_by(|(_, a), (_, b)| b.cmp(a));
+        debug!(top_three = ?&res[..res.len().min(3)]);
+        for (rank, (elf, calories)) in res.iter().take(3).enumerate() {
+            debug!(target: EXPLAIN, "#{}: elf {} with {calories} calories", rank + 1, elf + 1);
+        }
+
+        Ok(res.iter().take(3).map(|(_, calories)| calories).sum())
+    }
 }
 
 #[cfg(test)]
@@ -45,8 +65,7 @@ mod tests {
 9000
 
 10000";
-        println!("{}", part_1(INPUT));
-        assert_eq!(part_1(INPUT), 24000);
+        assert_eq!(Day1::part_1(&Day1::parse(INPUT).unwrap()).unwrap(), 24000);
     }
 
     #[test]
@@ -66,6 +85,14 @@ mod tests {
 
 10000";
 
-        assert_eq!(part_2(INPUT), 45000);
+        assert_eq!(Day1::part_2(&Day1::parse(INPUT).unwrap()).unwrap(), 45000);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = Day1::parse("1000\n20x0\n\n3000").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.snippet, "20x0");
     }
 }
@@ -1,16 +1,13 @@
-use itertools::Itertools;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::BTreeMap;
 
-use nom::{
-    branch::alt,
-    bytes::complete::{is_a, tag},
-    character::complete::{self, alpha1, digit1, newline},
-    combinator::verify,
-    multi::{many1, separated_list1},
-    sequence::{preceded, separated_pair},
-    *,
-};
-enum Instruction {
+use aoc_core::{finish, ParseError, Result, Solution};
+use grid::Grid;
+use nom::{branch::alt, bytes::complete::tag, combinator::cut, sequence::preceded, *};
+use parsers::{lines, number};
+use tracing::{debug, trace};
+use visualize::Rgb;
+
+pub enum Instruction {
     Noop,
     Add(i32),
 }
@@ -26,83 +23,100 @@ impl Instruction {
     }
 }
 
-pub fn part_1(input: &str) -> i32 {
-    let notable_cycles = [20, 60, 100, 140, 180, 220];
-    let mut scores: BTreeMap<u32, i32> = BTreeMap::new();
+const CRT_WIDTH: usize = 40;
 
-    let (_, instructions) = instruction_set(input).unwrap();
+const LIT: Rgb = [255, 255, 255];
+const BEAM: Rgb = [255, 60, 60];
 
-    let mut cycles: u32 = 0;
-    let mut x = 1;
-    for instruction in instructions.iter() {
-        if notable_cycles.contains(&(cycles + 1)) {
-            scores.insert(cycles + 1, (cycles as i32 + 1) * x);
-        }
+pub struct Day10;
 
-        if notable_cycles.contains(&(cycles + 2)) {
-            scores.insert(cycles + 2, (cycles as i32 + 2) * x);
-        }
-        cycles += instruction.cycles();
+impl Solution for Day10 {
+    type Input<'a> = Vec<Instruction>;
+    type Part1 = i32;
+    type Part2 = Grid<bool>;
 
-        match instruction {
-            Noop => {}
-            Add(n) => {
-                x += n;
-            }
-        };
+    fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+        finish(input, instruction_set(input))
     }
 
-    scores.iter().map(|(_, value)| value).sum::<i32>()
-}
+    fn part_1(instructions: &Vec<Instruction>) -> Result<i32> {
+        let notable_cycles = [20, 60, 100, 140, 180, 220];
+        let mut scores: BTreeMap<u32, i32> = BTreeMap::new();
 
-pub fn part_2(input: &str) -> String {
-    let (_, instructions) = instruction_set(input).unwrap();
+        let mut cycles: u32 = 0;
+        let mut x = 1;
+        for instruction in instructions.iter() {
+            if notable_cycles.contains(&(cycles + 1)) {
+                scores.insert(cycles + 1, (cycles as i32 + 1) * x);
+            }
 
-    let mut cycles: u32 = 0;
-    let mut x = 1;
-    let mut crt_pixels: String = "".to_string();
+            if notable_cycles.contains(&(cycles + 2)) {
+                scores.insert(cycles + 2, (cycles as i32 + 2) * x);
+            }
+            cycles += instruction.cycles();
+            trace!(cycles, x, "executed");
 
-    for instruction in instructions.iter() {
-        for cycle_add in 0..instruction.cycles() {
-            if ((x - 1)..=(x + 1)).contains(&((cycles as i32 + cycle_add as i32) % 40)) {
-                crt_pixels.push_str("#");
-            } else {
-                crt_pixels.push_str(".");
+            match instruction {
+                Noop => {}
+                Add(n) => {
+                    x += n;
+                }
             };
         }
 
-        cycles += instruction.cycles();
+        debug!(?scores, "signal strengths");
+        Ok(scores.values().sum::<i32>())
+    }
 
-        match instruction {
-            Noop => {}
-            Add(n) => {
-                x += n;
+    fn part_2(instructions: &Vec<Instruction>) -> Result<Grid<bool>> {
+        let total_cycles: u32 = instructions.iter().map(Instruction::cycles).sum();
+        let mut crt = Grid::filled(
+            CRT_WIDTH,
+            (total_cycles as usize).div_ceil(CRT_WIDTH),
+            false,
+        );
+
+        let mut cycles: usize = 0;
+        let mut x = 1;
+
+        for instruction in instructions.iter() {
+            for _ in 0..instruction.cycles() {
+                let pixel = (cycles % CRT_WIDTH, cycles / CRT_WIDTH);
+                crt[pixel] = ((x - 1)..=(x + 1)).contains(&(pixel.0 as i32));
+                trace!(cycle = cycles + 1, x, ?pixel, lit = crt[pixel]);
+                visualize::push(|| {
+                    let mut frame = crt.map(|&lit| if lit { LIT } else { visualize::BACKGROUND });
+                    frame[pixel] = BEAM;
+                    frame
+                });
+                cycles += 1;
             }
-        };
-    }
 
-    crt_pixels
-        .chars()
-        .chunks(40)
-        .into_iter()
-        .map(|chunk| chunk.collect::<String>())
-        .join("\n")
+            match instruction {
+                Noop => {}
+                Add(n) => {
+                    x += n;
+                }
+            };
+        }
+
+        Ok(crt)
+    }
 }
 
 fn instruction_set(input: &str) -> IResult<&str, Vec<Instruction>> {
-    let (input, vecs) = separated_list1(
-        newline,
-        alt((
-            tag("noop").map(|_| Noop),
-            preceded(tag("addx "), complete::i32).map(|num| Add(num)),
-        )),
-    )(input)?;
+    let (input, vecs) = lines(alt((
+        tag("noop").map(|_| Noop),
+        preceded(tag("addx "), cut(number)).map(Add),
+    )))(input)?;
 
     Ok((input, vecs))
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_core::Answer;
+
     use super::*;
     const INPUT: &str = "addx 15
 addx -11
@@ -253,14 +267,14 @@ noop";
 
     #[test]
     fn test_part1() {
-        let result = part_1(INPUT);
+        let result = Day10::part_1(&Day10::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, 13140);
     }
     #[test]
     fn test_part2() {
-        let result = part_2(INPUT);
+        let result = Day10::part_2(&Day10::parse(INPUT).unwrap()).unwrap();
         assert_eq!(
-            result,
+            Answer::from(result).to_string(),
             "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
@@ -1,7 +0,0 @@
-use day_11::part_1;
-use std::fs;
-
-fn main() {
-    let input = fs::read_to_string("input.txt").unwrap();
-    println!("{}", part_1(&input))
-}
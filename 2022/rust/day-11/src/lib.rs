@@ -1,36 +1,38 @@
 use std::collections::VecDeque;
 
+use aoc_core::{finish, Error, ParseError, Result, Solution, EXPLAIN};
 use nom::{
     branch::alt,
-    bytes::streaming::tag,
-    character::complete::{multispace1, newline},
-    multi::separated_list1,
-    number::complete,
-    sequence::{delimited, preceded},
+    bytes::complete::tag,
+    character::complete::{char, line_ending, space1},
+    combinator::verify,
+    sequence::{delimited, preceded, terminated},
     IResult, *,
 };
+use parsers::{blocks, field, number, numbers};
+use tracing::{debug, debug_span, info, trace, trace_span};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum Value {
     Old,
     Num(u64),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum Operation {
     Mul((Value, Value)),
     Add((Value, Value)),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Test {
     divisible: u64,
-    true_recipient: u64,
-    false_recipient: u64,
+    true_recipient: usize,
+    false_recipient: usize,
 }
 
-#[derive(Debug)]
-struct Monkey {
+#[derive(Clone, Debug)]
+pub struct Monkey {
     operation: Operation,
     items: VecDeque<u64>,
     test: Test,
@@ -38,7 +40,7 @@ struct Monkey {
 }
 
 impl Monkey {
-    fn inspect(&mut self, relief_lowers_worry_level: bool, magic_trick: u64) -> u64 {
+    fn inspect(&mut self, relief_lowers_worry_level: bool, magic_trick: u64) -> Result<u64> {
         self.touch_count += 1;
 
         let item = self.items.pop_front().unwrap();
@@ -55,7 +57,7 @@ impl Monkey {
                     Value::Num(num) => *num,
                 };
 
-                let result = num_a * num_b;
+                let result = num_a.checked_mul(num_b).ok_or_else(overflow)?;
 
                 result % magic_trick
             }
@@ -70,21 +72,21 @@ impl Monkey {
                     Value::Num(num) => *num,
                 };
 
-                let result = num_a + num_b;
+                let result = num_a.checked_add(num_b).ok_or_else(overflow)?;
 
                 result % magic_trick
             }
         };
 
         if relief_lowers_worry_level {
-            worry_level / 3
+            Ok(worry_level / 3)
         } else {
-            worry_level
+            Ok(worry_level)
         }
     }
 
-    fn test(&self, item: u64) -> u64 {
-        if item % self.test.divisible == 0 {
+    fn test(&self, item: u64) -> usize {
+        if item.is_multiple_of(self.test.divisible) {
             self.test.true_recipient
         } else {
             self.test.false_recipient
@@ -92,98 +94,94 @@ impl Monkey {
     }
 }
 
-pub fn part_1(input: &str) -> u64 {
-    let mut monkeys = input
-        .split("\n\n")
-        .into_iter()
-        .map(|s| monkey(s).unwrap().1)
-        .collect::<Vec<Monkey>>();
+fn overflow() -> Error {
+    Error::solve("worry level does not fit in 64 bits")
+}
 
-    let magic_trick = monkeys
-        .iter()
-        .map(|monkey| monkey.test.divisible)
-        .product::<u64>();
+pub struct Day11;
 
-    for _ in 0..20 {
-        for monkey_index in 0..monkeys.len() {
-            for _ in 0..monkeys[monkey_index].items.len() {
-                let monkey = monkeys.get_mut(monkey_index).unwrap();
-                let item = monkey.inspect(true, magic_trick);
-                let monkey_to_send_to = monkey.test(item);
+impl Solution for Day11 {
+    type Input<'a> = Vec<Monkey>;
+    type Part1 = u64;
+    type Part2 = u64;
 
-                monkeys
-                    .get_mut(monkey_to_send_to as usize)
-                    .unwrap()
-                    .items
-                    .push_back(item);
-            }
-        }
+    fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
+        let monkeys = finish(input, blocks(monkey)(input))?;
+        info!(monkeys = monkeys.len(), "parsed troop");
+        Ok(monkeys)
     }
 
-    monkeys.sort_by_key(|m| m.touch_count);
+    fn part_1(monkeys: &Vec<Monkey>) -> Result<u64> {
+        monkey_business(monkeys, 20, true)
+    }
 
-    monkeys
-        .iter()
-        .rev()
-        .take(2)
-        .map(|m| m.touch_count)
-        .product::<u64>()
+    fn part_2(monkeys: &Vec<Monkey>) -> Result<u64> {
+        monkey_business(monkeys, 10_000, false)
+    }
 }
 
-pub fn part_2(input: &str) -> u64 {
-    // let (_, mut monkeys) = separated_list1(tag("\n\n"), monkey)(input).unwrap();
-    let mut monkeys = input
-        .split("\n\n")
-        .into_iter()
-        .map(|s| monkey(s).unwrap().1)
-        .collect::<Vec<Monkey>>();
+fn monkey_business(
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief_lowers_worry_level: bool,
+) -> Result<u64> {
+    let mut monkeys = monkeys.to_vec();
 
     let magic_trick = monkeys
         .iter()
-        .map(|monkey| monkey.test.divisible)
-        .product::<u64>();
+        .try_fold(1u64, |product, monkey| {
+            product.checked_mul(monkey.test.divisible)
+        })
+        .ok_or_else(overflow)?;
+
+    for round in 1..=rounds {
+        let _round = debug_span!("round", round).entered();
 
-    for _ in 0..10_000 {
         for monkey_index in 0..monkeys.len() {
-            // println!("Monkey {monkey_index}:");
+            let _monkey = trace_span!("monkey", monkey = monkey_index).entered();
             for _ in 0..monkeys[monkey_index].items.len() {
                 let monkey = monkeys.get_mut(monkey_index).unwrap();
 
-                let item = monkey.inspect(false, magic_trick);
+                let item = monkey.inspect(relief_lowers_worry_level, magic_trick)?;
 
                 let monkey_to_send_to = monkey.test(item);
-                // println!("    Item with worry level {item} is thrown to monkey {monkey_to_send_to}.");
+                trace!(worry_level = item, to = monkey_to_send_to, "thrown");
+                debug!(
+                    target: EXPLAIN,
+                    "Monkey {monkey_index}: item with worry level {item} is thrown to monkey {monkey_to_send_to}."
+                );
                 monkeys
-                    .get_mut(monkey_to_send_to as usize)
-                    .unwrap()
+                    .get_mut(monkey_to_send_to)
+                    .ok_or_else(|| {
+                        Error::solve(format!(
+                            "monkey {monkey_index} throws to missing monkey {monkey_to_send_to}"
+                        ))
+                    })?
                     .items
                     .push_back(item);
             }
         }
+
+        debug!(inspections = ?monkeys.iter().map(|monkey| monkey.touch_count).collect::<Vec<_>>());
     }
+
+    info!(rounds, "simulated rounds");
     monkeys.sort_by_key(|monkey| monkey.touch_count);
-    monkeys
+    Ok(monkeys
         .iter()
         .rev()
         .take(2)
         .map(|monkey| monkey.touch_count)
-        .product::<u64>()
+        .product::<u64>())
 }
 
 fn monkey(input: &str) -> IResult<&str, Monkey> {
-    let (input, _id) = delimited(tag("Monkey "), nom::character::complete::u64, tag(":"))(input)?;
-
-    let (input, _) = multispace1(input)?;
-
-    let (input, items) = preceded(
-        tag("Starting items: "),
-        separated_list1(tag(", "), nom::character::complete::u64),
+    let (input, _id) = terminated(
+        delimited(tag("Monkey "), number::<u64>, char(':')),
+        line_ending,
     )(input)?;
-    let (input, _) = multispace1(input)?;
-
-    let (input, operation) = operation(input)?;
-    let (input, _) = multispace1(input)?;
-
+    let (input, items) = terminated(field("Starting items", numbers), line_ending)(input)?;
+    let (input, operation) = terminated(field("Operation", operation), line_ending)(input)?;
     let (input, test) = test(input)?;
 
     Ok((
@@ -198,39 +196,38 @@ fn monkey(input: &str) -> IResult<&str, Monkey> {
 }
 
 fn operation(input: &str) -> IResult<&str, Operation> {
-    let (input, _) = tag("Operation: new = ")(input)?;
+    let (input, _) = tag("new = ")(input)?;
 
     let (input, val1) = value(input)?;
-    let (input, operator) = delimited(multispace1, alt((tag("+"), tag("*"))), multispace1)(input)?;
+    let (input, operator) = delimited(space1, alt((tag("+"), tag("*"))), space1)(input)?;
     let (input, val2) = value(input)?;
 
     let result = match operator {
         "*" => Operation::Mul((val1, val2)),
         "+" => Operation::Add((val1, val2)),
-        _ => panic!("invalid operator"),
+        _ => unreachable!("only + and * are parsed"),
     };
 
     Ok((input, result))
 }
 
 fn test(input: &str) -> IResult<&str, Test> {
-    let (input, divisible) =
-        preceded(tag("Test: divisible by "), nom::character::complete::u64)(input)?;
-
-    let (input, _) = multispace1(input)?;
-
-    let (input, true_recipient) = preceded(
-        tag("If true: throw to monkey "),
-        nom::character::complete::u64,
+    let (input, divisible) = terminated(
+        field(
+            "Test",
+            preceded(tag("divisible by "), verify(number, |&divisor| divisor > 0)),
+        ),
+        line_ending,
     )(input)?;
 
-    let (input, _) = multispace1(input)?;
-
-    let (input, false_recipient) = preceded(
-        tag("If false: throw to monkey "),
-        nom::character::complete::u64,
+    let (input, true_recipient) = terminated(
+        field("If true", preceded(tag("throw to monkey "), number)),
+        line_ending,
     )(input)?;
 
+    let (input, false_recipient) =
+        field("If false", preceded(tag("throw to monkey "), number))(input)?;
+
     Ok((
         input,
         Test {
@@ -242,10 +239,7 @@ fn test(input: &str) -> IResult<&str, Test> {
 }
 
 fn value(input: &str) -> IResult<&str, Value> {
-    alt((
-        tag("old").map(|_| Value::Old),
-        nom::character::complete::u64.map(|v| Value::Num(v)),
-    ))(input)
+    alt((tag("old").map(|_| Value::Old), number.map(Value::Num)))(input)
 }
 
 #[cfg(test)]
@@ -256,13 +250,13 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        let result = part_1(INPUT);
+        let result = Day11::part_1(&Day11::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, 10605);
     }
 
     #[test]
     fn test_part_2() {
-        let res = part_2(INPUT);
+        let res = Day11::part_2(&Day11::parse(INPUT).unwrap()).unwrap();
         assert_eq!(res, 2713310158)
     }
 }
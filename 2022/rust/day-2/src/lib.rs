@@ -1,7 +1,10 @@
 use std::{cmp::Ordering, str::FromStr};
 
-#[derive(PartialEq)]
-enum Move {
+use aoc_core::{ParseError, Result, Solution, EXPLAIN};
+use tracing::{debug, trace};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Move {
     Rock = 1,
     Paper = 2,
     Scissors = 3,
@@ -30,59 +33,100 @@ impl FromStr for Move {
             "A" | "X" => Ok(Move::Rock),
             "B" | "Y" => Ok(Move::Paper),
             "C" | "Z" => Ok(Move::Scissors),
-            _ => Err("Unknown move".to_owned()),
+            _ => Err("unknown move, expected A, B, C, X, Y or Z".to_owned()),
         }
     }
 }
 
-pub fn part_2(input: &str) -> u32 {
-    let result: u32 = input
-        .lines()
-        .map(|line| {
-            let moves: Vec<&str> = line.split(" ").collect();
-            let oponent_move = moves[0].parse::<Move>().unwrap();
-
-            match moves[1] {
-                "X" => match oponent_move {
-                    Move::Paper => 1,
-                    Move::Rock => 3,
-                    Move::Scissors => 2,
-                },
-                "Y" => 3 + oponent_move as u32,
-                "Z" => {
-                    6 + match oponent_move {
-                        Move::Paper => 3,
-                        Move::Rock => 2,
-                        Move::Scissors => 1,
-                    }
-                }
-                _ => panic!("unexpected response"),
-            }
-        })
-        .sum();
+pub struct Day2;
 
-    result
-}
+impl Solution for Day2 {
+    type Input<'a> = Vec<(Move, Move)>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<(Move, Move)>, ParseError> {
+        let parse_move = |token: &str| {
+            token
+                .parse::<Move>()
+                .map_err(|error| ParseError::at(input, token, error))
+        };
+
+        input
+            .lines()
+            .map(|line| {
+                let (oponent_move, my_move) = line.split_once(' ').ok_or_else(|| {
+                    ParseError::at(input, line, "expected two moves separated by a space")
+                })?;
+
+                Ok((parse_move(oponent_move)?, parse_move(my_move)?))
+            })
+            .collect()
+    }
 
-pub fn part_1(input: &str) -> u32 {
-    let result: u32 = input
-        .lines()
-        .map(|line| {
-            let moves: Vec<Move> = line
-                .split(" ")
-                .map(|s| s.parse::<Move>().unwrap())
-                .collect();
-
-            match moves[0].partial_cmp(&moves[1]) {
-                Some(Ordering::Equal) => 3 + moves[1] as u32,
-                Some(Ordering::Less) => 6 + moves[1] as u32,
-                Some(Ordering::Greater) => 0 + moves[1] as u32,
-                None => panic!("failed to compare"),
-            }
-        })
-        .sum();
-
-    result
+    fn part_1(rounds: &Vec<(Move, Move)>) -> Result<u32> {
+        let score = rounds
+            .iter()
+            .enumerate()
+            .map(|(round, (oponent_move, my_move))| {
+                let (outcome, score) = match oponent_move.partial_cmp(my_move) {
+                    Some(Ordering::Equal) => ("draw", 3 + *my_move as u32),
+                    Some(Ordering::Less) => ("win", 6 + *my_move as u32),
+                    Some(Ordering::Greater) => ("loss", *my_move as u32),
+                    None => unreachable!("moves are always comparable"),
+                };
+                trace!(?oponent_move, ?my_move, score);
+                debug!(
+                    target: EXPLAIN,
+                    "round {}: {oponent_move:?} against my {my_move:?} is a {outcome}, {score} points",
+                    round + 1
+                );
+                score
+            })
+            .sum();
+
+        Ok(score)
+    }
+
+    // In part 2 the second column is the outcome: X (Rock) loses, Y (Paper)
+    // draws and Z (Scissors) wins.
+    fn part_2(rounds: &Vec<(Move, Move)>) -> Result<u32> {
+        let score = rounds
+            .iter()
+            .enumerate()
+            .map(|(round, (oponent_move, response))| {
+                let score = match response {
+                    Move::Rock => match oponent_move {
+                        Move::Paper => 1,
+                        Move::Rock => 3,
+                        Move::Scissors => 2,
+                    },
+                    Move::Paper => 3 + *oponent_move as u32,
+                    Move::Scissors => {
+                        6 + match oponent_move {
+                            Move::Paper => 3,
+                            Move::Rock => 2,
+                            Move::Scissors => 1,
+                        }
+                    }
+                };
+                trace!(?oponent_move, ?response, score);
+                let outcome = match response {
+                    Move::Rock => "lose",
+                    Move::Paper => "draw",
+                    Move::Scissors => "win",
+                };
+                debug!(
+                    target: EXPLAIN,
+                    "round {}: {oponent_move:?} and I must {outcome}, {score} points",
+                    round + 1
+                );
+                score
+            })
+            .sum();
+
+        Ok(score)
+    }
 }
 
 #[cfg(test)]
@@ -95,12 +139,12 @@ C Z";
 
     #[test]
     fn test_part_1() {
-        assert_eq!(part_1(INPUT), 15);
+        assert_eq!(Day2::part_1(&Day2::parse(INPUT).unwrap()).unwrap(), 15);
     }
 
     #[test]
     fn test_part_2() {
-        let result = part_2(INPUT);
+        let result = Day2::part_2(&Day2::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, 12);
     }
 }
@@ -1,7 +0,0 @@
-use day_3::part_2;
-
-fn main() {
-    let input = std::fs::read_to_string("input_2.txt").unwrap();
-
-    println!("{}", part_2(&input));
-}
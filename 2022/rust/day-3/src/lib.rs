@@ -1,53 +1,100 @@
-#![feature(iter_array_chunks)]
-
-use std::collections::HashMap;
-
-pub fn part_1(input: &str) -> usize {
-    let letters = ('a'..='z')
-        .into_iter()
-        .chain('A'..='Z')
-        .enumerate()
-        .map(|(idx, c)| (c, idx + 1))
-        .collect::<HashMap<char, usize>>();
-
-    let result = input
-        .lines()
-        .map(|line| {
-            let sack_length = line.len() / 2;
-            let compartment_a = &line[0..sack_length];
-            let compartment_b = &line[sack_length..];
-
-            let common_char = compartment_a
-                .chars()
-                .find(|c| compartment_b.contains(*c))
-                .unwrap();
-
-            letters.get(&common_char).unwrap()
-        })
-        .sum::<usize>();
-
-    result
+use aoc_core::{Error, ParseError, Result, Solution, EXPLAIN};
+use tracing::{debug, trace};
+
+/// a to z are 1 to 26, A to Z are 27 to 52.
+fn priority(item: char) -> usize {
+    match item {
+        'a'..='z' => item as usize - 'a' as usize + 1,
+        'A'..='Z' => item as usize - 'A' as usize + 27,
+        _ => unreachable!("parse only accepts letters"),
+    }
+}
+
+/// The set of item types in a rucksack, one bit per priority.
+fn items(rucksack: &str) -> u64 {
+    rucksack
+        .chars()
+        .fold(0, |items, item| items | 1 << priority(item))
+}
+
+/// The first item of `rucksack` whose type is in `items`.
+fn first_of(rucksack: &str, items: u64) -> Option<char> {
+    rucksack
+        .chars()
+        .find(|&item| items & 1 << priority(item) != 0)
 }
 
-pub fn part_2(input: &str) -> usize {
-    let letters = ('a'..='z')
-        .into_iter()
-        .chain('A'..='Z')
-        .enumerate()
-        .map(|(idx, c)| (c, idx + 1))
-        .collect::<HashMap<char, usize>>();
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = Vec<&'a str>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-   let sum = input.lines().array_chunks::<3>().map(|[a, b, c]| {
-       let c_char = a.chars().find(|ch| b.contains(*ch) && c.contains(*ch)).unwrap();
+    fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+        input
+            .lines()
+            .map(
+                |line| match line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                    Some((idx, c)) => Err(ParseError::at(
+                        input,
+                        &line[idx..idx + c.len_utf8()],
+                        "expected an item letter",
+                    )),
+                    None => Ok(line),
+                },
+            )
+            .collect()
+    }
 
-       letters.get(&c_char).unwrap()
-    }).sum::<usize>();
+    fn part_1(rucksacks: &Vec<&str>) -> Result<usize> {
+        rucksacks
+            .iter()
+            .map(|line| {
+                let sack_length = line.len() / 2;
+                let compartment_a = &line[0..sack_length];
+                let compartment_b = &line[sack_length..];
 
-    sum
+                let common_char = first_of(compartment_a, items(compartment_b))
+                    .ok_or_else(|| Error::solve(format!("no item in both halves of {line}")))?;
+                trace!(rucksack = line, item = %common_char);
+                debug!(
+                    target: EXPLAIN,
+                    "{compartment_a} {compartment_b} both hold {common_char}, priority {}",
+                    priority(common_char)
+                );
+
+                Ok(priority(common_char))
+            })
+            .sum::<Result<usize>>()
+    }
+
+    fn part_2(rucksacks: &Vec<&str>) -> Result<usize> {
+        rucksacks
+            .chunks_exact(3)
+            .map(|group| {
+                let [a, b, c] = group else {
+                    unreachable!("chunks_exact yields groups of three")
+                };
+                let c_char = first_of(a, items(b) & items(c))
+                    .ok_or_else(|| Error::solve(format!("no badge shared by {a}, {b} and {c}")))?;
+                trace!(group = ?[a, b, c], badge = %c_char);
+                debug!(
+                    target: EXPLAIN,
+                    "{a}, {b} and {c} all hold {c_char}, priority {}",
+                    priority(c_char)
+                );
+
+                Ok(priority(c_char))
+            })
+            .sum::<Result<usize>>()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::{collection::vec, prelude::*};
+
     use super::*;
 
     const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
@@ -59,13 +106,46 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[test]
     fn test_part_1() {
-        let result = part_1(INPUT);
+        let result = Day3::part_1(&Day3::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, 157);
     }
 
     #[test]
     fn test_part_2() {
-        let result = part_2(INPUT);
+        let result = Day3::part_2(&Day3::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, 70);
     }
+
+    /// Scans the other rucksacks for each item, as the solution first did.
+    fn reference(rucksacks: &[&str]) -> (Option<usize>, Option<usize>) {
+        let compartments = rucksacks
+            .iter()
+            .map(|line| {
+                let (a, b) = line.split_at(line.len() / 2);
+                a.chars().find(|c| b.contains(*c)).map(priority)
+            })
+            .sum();
+        let badges = rucksacks
+            .chunks_exact(3)
+            .map(|group| {
+                let [a, b, c] = group else { unreachable!() };
+                a.chars()
+                    .find(|ch| b.contains(*ch) && c.contains(*ch))
+                    .map(priority)
+            })
+            .sum();
+        (compartments, badges)
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_reference(rucksacks in vec("[a-eA-E]{2,12}", 0..12)) {
+            let rucksacks: Vec<&str> = rucksacks.iter().map(String::as_str).collect();
+
+            prop_assert_eq!(
+                (Day3::part_1(&rucksacks).ok(), Day3::part_2(&rucksacks).ok()),
+                reference(&rucksacks)
+            );
+        }
+    }
 }
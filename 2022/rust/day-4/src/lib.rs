@@ -1,58 +1,81 @@
 use std::ops::RangeInclusive;
 
+use aoc_core::{finish, ParseError, Result, Solution};
 use nom::IResult;
 
-use nom::character::complete::newline;
-use nom::multi::separated_list1;
+use nom::bytes::complete::tag;
+use nom::combinator::cut;
 use nom::sequence::separated_pair;
-use nom::{bytes::complete::tag, character::complete};
+use parsers::{lines, number};
+use tracing::trace;
 
 fn sections(input: &str) -> IResult<&str, RangeInclusive<u32>> {
-    let (input, start) = complete::u32(input)?;
-    let (input, _) = tag("-")(input)?;
-    let (input, end) = complete::u32(input)?;
+    let (input, start) = number(input)?;
+    let (input, _) = cut(tag("-"))(input)?;
+    let (input, end) = cut(number)(input)?;
     Ok((input, start..=end))
 }
 
-fn line(input: &str) -> IResult<&str, (RangeInclusive<u32>, RangeInclusive<u32>)> {
-    let (input, (start, end)) = separated_pair(sections, tag(","), sections)(input)?;
+pub type Assignment = (RangeInclusive<u32>, RangeInclusive<u32>);
+
+fn line(input: &str) -> IResult<&str, Assignment> {
+    let (input, (start, end)) = separated_pair(sections, cut(tag(",")), cut(sections))(input)?;
     Ok((input, (start, end)))
 }
 
-fn section_assigment(
-    input: &str,
-) -> IResult<&str, Vec<(RangeInclusive<u32>, RangeInclusive<u32>)>> {
-    let (input, ranges) = separated_list1(newline, line)(input)?;
+pub struct Day4;
 
-    Ok((input, ranges))
-}
+impl Solution for Day4 {
+    type Input<'a> = Vec<Assignment>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Assignment>, ParseError> {
+        finish(input, lines(line)(input))
+    }
+
+    fn part_1(assigments: &Vec<Assignment>) -> Result<usize> {
+        let count = assigments
+            .iter()
+            .filter(|(range_x, range_y)| {
+                let contained = contains(range_x, range_y) || contains(range_y, range_x);
+                trace!(?range_x, ?range_y, contained);
+                contained
+            })
+            .count();
 
-pub fn part_1(input: &str) -> usize {
-    let (_, assigments) = section_assigment(input).unwrap();
+        Ok(count)
+    }
+
+    fn part_2(assigments: &Vec<Assignment>) -> Result<usize> {
+        let count = assigments
+            .iter()
+            .filter(|(range_x, range_y)| {
+                let overlapping = overlaps(range_x, range_y);
+                trace!(?range_x, ?range_y, overlapping);
+                overlapping
+            })
+            .count();
 
-    assigments
-        .iter()
-        .filter(|(range_x, range_y)| {
-            range_x.clone().into_iter().all(|n| range_y.contains(&n))
-                || range_y.clone().into_iter().all(|n| range_x.contains(&n))
-        })
-        .count()
+        Ok(count)
+    }
 }
 
-pub fn part_2(input: &str) -> usize {
-    let (_, assigments) = section_assigment(input).unwrap();
+/// Whether every section of `inner` is also in `outer`, so an empty `inner`
+/// always is.
+fn contains(outer: &RangeInclusive<u32>, inner: &RangeInclusive<u32>) -> bool {
+    inner.is_empty() || (outer.start() <= inner.start() && inner.end() <= outer.end())
+}
 
-    assigments
-        .iter()
-        .filter(|(range_x, range_y)| {
-            range_x.clone().into_iter().any(|n| range_y.contains(&n))
-                || range_y.clone().into_iter().any(|n| range_x.contains(&n))
-        })
-        .count()
+/// Whether the two ranges share at least one section.
+fn overlaps(x: &RangeInclusive<u32>, y: &RangeInclusive<u32>) -> bool {
+    !x.is_empty() && !y.is_empty() && x.start() <= y.end() && y.start() <= x.end()
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::{collection::vec, prelude::*};
+
     use super::*;
 
     const INPUT: &str = "2-4,6-8
@@ -64,13 +87,52 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        let result = part_1(INPUT);
+        let result = Day4::part_1(&Day4::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, 2);
     }
 
     #[test]
     fn test_part_2() {
-        let result = part_2(INPUT);
+        let result = Day4::part_2(&Day4::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, 4);
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = Day4::parse("2-4,6-8\n2-3,x-5\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.message, "expected a number");
+    }
+
+    /// Section by section, as the solution first did.
+    fn reference(assignments: &[Assignment]) -> (usize, usize) {
+        let contained = assignments
+            .iter()
+            .filter(|(x, y)| x.clone().all(|n| y.contains(&n)) || y.clone().all(|n| x.contains(&n)))
+            .count();
+        let overlapping = assignments
+            .iter()
+            .filter(|(x, y)| x.clone().any(|n| y.contains(&n)) || y.clone().any(|n| x.contains(&n)))
+            .count();
+        (contained, overlapping)
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_reference(
+            // Includes empty ranges such as 5-3.
+            pairs in vec(((0..60u32, 0..60u32), (0..60u32, 0..60u32)), 0..50)
+        ) {
+            let assignments: Vec<Assignment> = pairs
+                .into_iter()
+                .map(|((a, b), (c, d))| (a..=b, c..=d))
+                .collect();
+
+            prop_assert_eq!(
+                (Day4::part_1(&assignments).unwrap(), Day4::part_2(&assignments).unwrap()),
+                reference(&assignments)
+            );
+        }
+    }
 }
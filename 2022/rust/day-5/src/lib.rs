@@ -1,16 +1,22 @@
 use std::vec;
 
+use aoc_core::{finish, Error, ParseError, Result, Solution, EXPLAIN};
+use grid::Grid;
 use nom::{
     branch::alt,
-    bytes::streaming::tag,
-    character::complete::{self, alpha1, anychar, digit1, line_ending, multispace1, newline},
+    bytes::complete::tag,
+    character::complete::{self, alpha1, digit1, line_ending, multispace1},
+    combinator::{consumed, cut, verify},
     multi::{many1, separated_list1},
-    sequence::{delimited, preceded, terminated},
+    sequence::{delimited, preceded},
     IResult,
 };
+use parsers::{lines, number};
+use tracing::{debug, info, trace};
+use visualize::Frame;
 
 #[derive(Debug)]
-struct Move {
+pub struct Move {
     number: usize,
     from: usize,
     to: usize,
@@ -37,14 +43,18 @@ fn line(input: &str) -> IResult<&str, Vec<Option<&str>>> {
     Ok((input, result))
 }
 
+fn stack(input: &str) -> IResult<&str, u32> {
+    verify(number, |&stack| stack > 0)(input)
+}
+
 fn get_move(input: &str) -> IResult<&str, Move> {
     let (input, _) = tag("move ")(input)?;
-    let (input, number) = complete::u32(input)?;
-    let (input, _) = tag(" from ")(input)?;
+    let (input, number) = cut(number::<u32>)(input)?;
+    let (input, _) = cut(tag(" from "))(input)?;
 
-    let (input, from) = complete::u32(input)?;
-    let (input, _) = tag(" to ")(input)?;
-    let (input, to) = complete::u32(input)?;
+    let (input, from) = cut(stack)(input)?;
+    let (input, _) = cut(tag(" to "))(input)?;
+    let (input, to) = cut(stack)(input)?;
 
     Ok((
         input,
@@ -56,72 +66,134 @@ fn get_move(input: &str) -> IResult<&str, Move> {
     ))
 }
 
-fn cells(input: &str) -> IResult<&str, (Vec<Vec<&str>>, Vec<Move>)> {
-    let (input, cells_horizontal) = separated_list1(newline, line)(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _nums) = many1(preceded(multispace1, digit1))(input)?;
+/// A row of the drawing, with the text it was parsed from.
+type Row<'a> = (&'a str, Vec<Option<&'a str>>);
+
+fn cells(input: &str) -> IResult<&str, (Vec<Row<'_>>, usize, Vec<Move>)> {
+    let (input, rows) = lines(consumed(line))(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, nums) = many1(preceded(multispace1, digit1))(input)?;
     let (input, _) = multispace1(input)?;
 
-    let (input, moves) = separated_list1(newline, get_move)(input)?;
+    let (input, moves) = lines(get_move)(input)?;
 
-    let mut cells_vertical: Vec<Vec<Option<&str>>> = vec![Vec::new(); cells_horizontal.len() + 1];
-    for vec in cells_horizontal.iter().rev() {
-        for (i, c) in vec.iter().enumerate() {
-            cells_vertical[i].push(c.clone());
+    Ok((input, (rows, nums.len(), moves)))
+}
+
+/// Stacks the drawing's crates bottom first, one stack per number below it.
+fn stacks<'a>(
+    source: &'a str,
+    rows: &[Row<'a>],
+    count: usize,
+) -> Result<Vec<Vec<&'a str>>, ParseError> {
+    let mut cells_vertical: Vec<Vec<&str>> = vec![Vec::new(); count];
+    for (text, row) in rows.iter().rev() {
+        if row.len() > count {
+            return Err(ParseError::at(
+                source,
+                text,
+                format!("row has {} crates but there are {count} stacks", row.len()),
+            ));
+        }
+        for (stack, cell) in cells_vertical.iter_mut().zip(row) {
+            stack.extend(*cell);
         }
     }
-    dbg!(input, &moves, &cells_vertical);
-
-    Ok((
-        input,
-        (
-            cells_vertical
-                .iter()
-                .map(|v| v.iter().filter_map(|v| *v).collect())
-                .collect(),
-            moves,
-        ),
-    ))
+    Ok(cells_vertical)
 }
 
-pub fn part_1(input: &str) -> String {
-    let (_, (mut cells, moves)) = cells(input).unwrap();
-
-    for Move { to, from, number } in moves.iter() {
-        let len = cells[*from as usize].len();
-        for c in cells[*from as usize]
-            .drain((len - *number as usize)..)
-            .rev()
-            .collect::<Vec<&str>>()
-            .iter()
-        {
-            cells[*to as usize].push(c)
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input<'a> = (Vec<Vec<&'a str>>, Vec<Move>);
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<(Vec<Vec<&str>>, Vec<Move>), ParseError> {
+        let (rows, count, moves) = finish(input, cells(input))?;
+        let cells_vertical = stacks(input, &rows, count)?;
+        info!(stacks = count, moves = moves.len(), "parsed drawing");
+        trace!(?cells_vertical);
+
+        Ok((cells_vertical, moves))
+    }
+
+    fn part_1((cells, moves): &(Vec<Vec<&str>>, Vec<Move>)) -> Result<String> {
+        let mut cells = cells.clone();
+        visualize::push(|| draw(&cells));
+
+        for Move { to, from, number } in moves.iter() {
+            let crates = take(&mut cells, *from, *number)?;
+            stack_mut(&mut cells, *to)?.extend(crates.iter().rev());
+            visualize::push(|| draw(&cells));
+            trace!(
+                number,
+                from = from + 1,
+                to = to + 1,
+                ?cells,
+                "moved one at a time"
+            );
+            debug!(
+                target: EXPLAIN,
+                "move {number} from {} to {} one at a time, tops are now {}",
+                from + 1,
+                to + 1,
+                top_crates(&cells)
+            );
         }
+
+        Ok(top_crates(&cells))
     }
 
+    fn part_2((cells, moves): &(Vec<Vec<&str>>, Vec<Move>)) -> Result<String> {
+        let mut cells = cells.clone();
+        visualize::push(|| draw(&cells));
+
+        for Move { to, from, number } in moves.iter() {
+            let crates = take(&mut cells, *from, *number)?;
+            stack_mut(&mut cells, *to)?.extend(crates);
+            visualize::push(|| draw(&cells));
+            trace!(
+                number,
+                from = from + 1,
+                to = to + 1,
+                ?cells,
+                "moved together"
+            );
+            debug!(
+                target: EXPLAIN,
+                "move {number} from {} to {} together, tops are now {}",
+                from + 1,
+                to + 1,
+                top_crates(&cells)
+            );
+        }
+
+        Ok(top_crates(&cells))
+    }
+}
+
+fn stack_mut<'c, 'a>(cells: &'c mut [Vec<&'a str>], index: usize) -> Result<&'c mut Vec<&'a str>> {
     cells
-        .iter()
-        .map(|v| match v.iter().last() {
-            Some(c) => c,
-            None => "",
-        })
-        .collect()
+        .get_mut(index)
+        .ok_or_else(|| Error::solve(format!("there is no stack {}", index + 1)))
 }
 
-pub fn part_2(input: &str) -> String {
-    let (_, (mut cells, moves)) = cells(input).unwrap();
-
-    for Move { to, from, number } in moves.iter() {
-        let len = cells[*from as usize].len();
-        for c in cells[*from as usize]
-            .drain((len - *number as usize)..)
-            .collect::<Vec<&str>>()
-            .iter()
-        {
-            cells[*to as usize].push(c)
-        }
+/// Lifts the top `number` crates off a stack, bottom crate first.
+fn take<'a>(cells: &mut [Vec<&'a str>], from: usize, number: usize) -> Result<Vec<&'a str>> {
+    let stack = stack_mut(cells, from)?;
+    if number > stack.len() {
+        return Err(Error::solve(format!(
+            "cannot move {number} crates from stack {}, it only has {}",
+            from + 1,
+            stack.len()
+        )));
     }
 
+    Ok(stack.split_off(stack.len() - number))
+}
+
+fn top_crates(cells: &[Vec<&str>]) -> String {
     cells
         .iter()
         .map(|v| match v.iter().last() {
@@ -131,6 +203,25 @@ pub fn part_2(input: &str) -> String {
         .collect()
 }
 
+/// The stacks side by side, tall enough to hold every crate in one, each
+/// crate coloured by its letter.
+fn draw(cells: &[Vec<&str>]) -> Frame {
+    let height = cells.iter().map(Vec::len).sum::<usize>().max(1);
+    let mut frame = Grid::filled((cells.len() * 2).max(2) - 1, height, visualize::BACKGROUND);
+
+    for (x, stack) in cells.iter().enumerate() {
+        for (level, label) in stack.iter().enumerate() {
+            let letter = label.bytes().next().unwrap_or(b'A').wrapping_sub(b'A');
+            frame[(x * 2, height - 1 - level)] = [
+                80 + letter.wrapping_mul(7) % 176,
+                200 - letter.wrapping_mul(5) % 150,
+                100 + letter.wrapping_mul(37) % 150,
+            ];
+        }
+    }
+    frame
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,13 +238,21 @@ move 1 from 1 to 2";
 
     #[test]
     fn test_part_1() {
-        let result = part_1(INPUT);
+        let result = Day5::part_1(&Day5::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, "CMZ");
     }
 
     #[test]
     fn test_part_2() {
-        let result = part_2(INPUT);
+        let result = Day5::part_2(&Day5::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, "MCD");
     }
+
+    #[test]
+    fn test_more_crates_than_stacks() {
+        let error = Day5::parse("[A] [B] [C]\n 1   2 \n\nmove 1 from 1 to 2").unwrap_err();
+
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.message, "row has 3 crates but there are 2 stacks");
+    }
 }
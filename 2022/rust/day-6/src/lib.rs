@@ -1,27 +1,63 @@
-use itertools::Itertools;
+use aoc_core::{Error, ParseError, Result, Solution};
+use tracing::debug;
 
-pub fn part_1(input: &str) -> usize {
-    find_unique_sequence(input, 4)
-}
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input<'a> = &'a [u8];
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<&[u8], ParseError> {
+        Ok(input.trim_end().as_bytes())
+    }
+
+    fn part_1(datastream: &&[u8]) -> Result<usize> {
+        find_unique_sequence(datastream, 4)
+    }
 
-pub fn part_2(input: &str) -> usize {
-    find_unique_sequence(input, 14)
+    fn part_2(datastream: &&[u8]) -> Result<usize> {
+        find_unique_sequence(datastream, 14)
+    }
 }
 
-fn find_unique_sequence(input: &str, size: usize) -> usize {
-    let seq = input
-        .as_bytes()
-        .windows(size)
-        .position(|chars| chars.iter().all_unique())
-        .map(|idx| idx + size)
-        .unwrap();
-    seq
+/// Position just past the first `size` distinct characters in a row, found in
+/// one pass by remembering where each character was last seen.
+fn find_unique_sequence(datastream: &[u8], size: usize) -> Result<usize> {
+    let mut seen_before = [0; 256];
+    // Start of the longest run of distinct characters ending at `end`.
+    let mut start = 0;
+
+    for (end, &char) in (1..).zip(datastream) {
+        start = start.max(seen_before[char as usize]);
+        seen_before[char as usize] = end;
+
+        if end - start >= size {
+            debug!(size, marker = %String::from_utf8_lossy(&datastream[end - size..end]), end);
+            return Ok(end);
+        }
+    }
+
+    Err(Error::solve(format!(
+        "no {size} distinct characters in a row"
+    )))
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
     use super::*;
 
+    fn part_1(input: &str) -> usize {
+        Day6::part_1(&Day6::parse(input).unwrap()).unwrap()
+    }
+
+    fn part_2(input: &str) -> usize {
+        Day6::part_2(&Day6::parse(input).unwrap()).unwrap()
+    }
+
     #[test]
     fn test_part_1() {
         assert_eq!(part_1("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
@@ -38,4 +74,24 @@ mod tests {
         assert_eq!(part_2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
         assert_eq!(part_2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
     }
+
+    /// Checks every window, as the solution first did.
+    fn reference(datastream: &[u8], size: usize) -> Option<usize> {
+        datastream
+            .windows(size)
+            .position(|chars| chars.iter().all_unique())
+            .map(|idx| idx + size)
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_reference(datastream in "[a-p]{0,100}", size in 1..=16usize) {
+            let datastream = datastream.as_bytes();
+
+            prop_assert_eq!(
+                find_unique_sequence(datastream, size).ok(),
+                reference(datastream, size)
+            );
+        }
+    }
 }
@@ -1,20 +1,22 @@
-#![feature(iter_intersperse)]
 use std::collections::BTreeMap;
 
+use aoc_core::{finish, Error, ParseError, Result, Solution, EXPLAIN};
 use nom::{
     branch::alt,
-    bytes::{complete::is_a, streaming::tag},
-    character::complete::{alpha1, newline},
+    bytes::complete::{tag, take_till1},
+    character::complete::{line_ending, space0, space1},
+    combinator::{cut, recognize},
     multi::separated_list1,
-    number::complete,
     sequence::separated_pair,
     IResult,
 };
+use parsers::{lines, number};
+use tracing::{debug, info, trace};
 
 #[derive(Debug)]
 enum Operation<'a> {
     Cd(Cd<'a>),
-    Ls(Vec<Files<'a>>),
+    Ls(Vec<Files>),
 }
 
 #[derive(Debug)]
@@ -25,29 +27,38 @@ enum Cd<'a> {
 }
 
 #[derive(Debug)]
-enum Files<'a> {
-    File { size: u32, name: &'a str },
-    Dir(&'a str),
+enum Files {
+    File { size: u64 },
+    Dir,
 }
 
-fn file(input: &str) -> IResult<&str, Files> {
-    use nom::character::complete;
+/// A file or directory name: anything up to the end of the line, spaces
+/// included but trailing ones left out.
+fn name(input: &str) -> IResult<&str, &str> {
+    recognize(separated_list1(space1, take_till1(char::is_whitespace)))(input)
+}
 
-    let (input, (size, name)) =
-        separated_pair(complete::u32, tag(" "), is_a("qwertyuiopasdfghjklzxcvbnm."))(input)?;
+fn file(input: &str) -> IResult<&str, Files> {
+    let (input, (size, _name)) = separated_pair(number::<u32>, tag(" "), name)(input)?;
 
-    Ok((input, Files::File { size, name }))
+    Ok((
+        input,
+        Files::File {
+            size: u64::from(size),
+        },
+    ))
 }
 
 fn dir(input: &str) -> IResult<&str, Files> {
     let (input, _) = tag("dir ")(input)?;
-    let (input, name) = alpha1(input)?;
-    Ok((input, Files::Dir(name)))
+    let (input, _name) = name(input)?;
+    Ok((input, Files::Dir))
 }
 
-fn cd(input: &str) -> IResult<&str, Operation> {
+fn cd(input: &str) -> IResult<&str, Operation<'_>> {
     let (input, _) = tag("$ cd ")(input)?;
-    let (input, dir) = alt((tag(".."), tag("/"), alpha1))(input)?;
+    let (input, dir) = cut(name)(input)?;
+    let (input, _) = space0(input)?;
     Ok((
         input,
         match dir {
@@ -58,41 +69,107 @@ fn cd(input: &str) -> IResult<&str, Operation> {
     ))
 }
 
-fn ls(input: &str) -> IResult<&str, Operation> {
+fn ls(input: &str) -> IResult<&str, Operation<'_>> {
     let (input, _) = tag("$ ls")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, files) = separated_list1(newline, alt((file, dir)))(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, files) = lines(alt((file, dir)))(input)?;
 
     Ok((input, Operation::Ls(files)))
 }
 
-fn commands(input: &str) -> IResult<&str, Vec<Operation>> {
-    let (input, cmds) = separated_list1(newline, alt((cd, ls)))(input)?;
+fn commands(input: &str) -> IResult<&str, Vec<Operation<'_>>> {
+    let (input, cmds) = lines(alt((cd, ls)))(input)?;
     Ok((input, cmds))
 }
 
-#[derive(Debug)]
-struct File<'a> {
-    size: u32,
-    name: &'a str,
-}
+/// Total size of every directory, keyed by its path from the root.
+pub type Sizes<'a> = BTreeMap<Vec<&'a str>, u64>;
+
+pub struct Day7;
 
-pub fn part_1(input: &str) -> u32 {
-    let (_, cmds) = commands(input).unwrap();
+impl Solution for Day7 {
+    type Input<'a> = Sizes<'a>;
+    type Part1 = u64;
+    type Part2 = u64;
 
-    let (_, sizes) = cmds.iter().fold((vec![], BTreeMap::new()), calculate_sizes);
+    fn parse(input: &str) -> Result<Sizes<'_>, ParseError> {
+        let cmds = finish(input, commands(input))?;
+
+        let (_, sizes) = cmds.iter().fold((vec![], BTreeMap::new()), calculate_sizes);
+        info!(
+            commands = cmds.len(),
+            directories = sizes.len(),
+            "replayed transcript"
+        );
+        Ok(sizes)
+    }
 
-    sizes
-        .iter()
-        .map(|(_, size)| size)
-        .filter(|&&size| size < 100000)
-        .sum::<u32>()
+    fn part_1(sizes: &Sizes) -> Result<u64> {
+        Ok(sizes
+            .iter()
+            .filter(|(dir, &size)| {
+                let small = size < 100000;
+                debug!(
+                    target: EXPLAIN,
+                    "{} holds {size} bytes{}",
+                    path(dir),
+                    if small { ", counted" } else { "" }
+                );
+                small
+            })
+            .map(|(_, size)| size)
+            .sum::<u64>())
+    }
+
+    fn part_2(sizes: &Sizes) -> Result<u64> {
+        let total_size: u64 = 70_000_000;
+        let needed_space: u64 = 30_000_000;
+
+        let used_space = sizes
+            .get(&vec![""])
+            .ok_or_else(|| Error::solve("the root directory is never listed"))?;
+
+        let current_free_space = total_size
+            .checked_sub(*used_space)
+            .ok_or_else(|| Error::solve(format!("{used_space} bytes do not fit on the disk")))?;
+        let need_to_free_at_least = needed_space.saturating_sub(current_free_space);
+        debug!(
+            target: EXPLAIN,
+            "{used_space} bytes are used, so at least {need_to_free_at_least} must be freed"
+        );
+
+        let mut valid_dirs = sizes
+            .iter()
+            .filter(|(_, &size)| size > need_to_free_at_least)
+            .collect::<Vec<_>>();
+
+        valid_dirs.sort_by_key(|(_, &size)| size);
+        let (dir, &size) = valid_dirs
+            .first()
+            .ok_or_else(|| Error::solve("no directory is large enough to delete"))?;
+        debug!(
+            target: EXPLAIN,
+            "{} of {} large enough directories is the smallest, {size} bytes",
+            path(dir),
+            valid_dirs.len()
+        );
+
+        Ok(size)
+    }
+}
+
+/// A directory's path, `/` for the root.
+fn path(dir: &[&str]) -> String {
+    match dir {
+        [""] => "/".to_string(),
+        _ => dir.join("/"),
+    }
 }
 
 fn calculate_sizes<'a>(
-    (mut context, mut sizes): (Vec<&'a str>, BTreeMap<Vec<&'a str>, u32>),
-    command: &'a Operation,
-) -> (Vec<&'a str>, BTreeMap<Vec<&'a str>, u32>) {
+    (mut context, mut sizes): (Vec<&'a str>, Sizes<'a>),
+    command: &Operation<'a>,
+) -> (Vec<&'a str>, Sizes<'a>) {
     match command {
         Operation::Cd(Cd::Root) => {
             context.push("");
@@ -102,7 +179,7 @@ fn calculate_sizes<'a>(
         }
 
         Operation::Cd(Cd::Down(name)) => {
-            context.push(name);
+            context.push(*name);
         }
         Operation::Ls(files) => {
             let sum = files
@@ -114,8 +191,9 @@ fn calculate_sizes<'a>(
                         None
                     }
                 })
-                .sum::<u32>();
+                .sum::<u64>();
 
+            trace!(dir = %path(&context), files = files.len(), size = sum, "listed");
             for i in 0..context.len() {
                 sizes
                     .entry(context[0..=i].to_vec())
@@ -128,29 +206,6 @@ fn calculate_sizes<'a>(
     (context, sizes)
 }
 
-pub fn part_2(input: &str) -> u32 {
-    let (_, cmds) = commands(input).unwrap();
-
-    let (_, sizes) = cmds.iter().fold((vec![], BTreeMap::new()), calculate_sizes);
-
-    let total_size = 70_000_000;
-    let needed_space = 30_000_000;
-
-    let used_space = sizes.get(&vec![""]).unwrap();
-
-    let current_free_space = total_size - used_space;
-    let need_to_free_at_least = needed_space - current_free_space;
-
-    let mut valid_dirs = sizes
-        .iter()
-        .filter(|(_, &size)| size > need_to_free_at_least)
-        .map(|(_, size)| size)
-        .collect::<Vec<&u32>>();
-
-    valid_dirs.sort();
-    **valid_dirs.iter().next().unwrap()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,14 +236,25 @@ $ ls
 
     #[test]
     fn test_part_1() {
-        let result = part_1(INPUT);
+        let result = Day7::part_1(&Day7::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, 95437);
     }
 
     #[test]
     fn test_part_2() {
-        let result = part_2(INPUT);
+        let result = Day7::part_2(&Day7::parse(INPUT).unwrap()).unwrap();
 
         assert_eq!(result, 24933642);
     }
+
+    #[test]
+    fn test_any_file_name() {
+        let sizes = Day7::parse(
+            "$ cd /\n$ ls\ndir Src 2\n10 Cargo.toml\n$ cd Src 2\n$ ls\n5 main 1.rs\n$ cd ..",
+        )
+        .unwrap();
+
+        assert_eq!(sizes[&vec!["", "Src 2"]], 5);
+        assert_eq!(sizes[&vec![""]], 15);
+    }
 }
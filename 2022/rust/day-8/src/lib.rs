@@ -1,150 +1,120 @@
-use nom::{
-    character::complete::{digit1, newline},
-    multi::{many1, separated_list1},
-    *,
-};
-
-pub fn part_1(input: &str) -> usize {
-    let (_, trees) = parse_trees(input).unwrap();
-
-    let max_length = trees.len() - 1;
-    let mut visible_trees: Vec<Vec<bool>> = trees
-        .iter()
-        .enumerate()
-        .map(|(i, tree_line)| {
-            let line_max_length = tree_line.len() - 1;
-
-            tree_line
-                .iter()
-                .enumerate()
-                .map(|(line_idx, _)| {
-                    i == 0 || i == max_length || line_idx == 0 || line_idx == line_max_length
-                })
-                .collect()
-        })
-        .collect();
-
-    dbg!(&visible_trees, (0..trees.len()).next());
-
-    for y in 0..trees.len() {
-        let mut current_tree_size = 0;
-        for x in 0..trees[0].len() {
-            if x == 0 {
-                current_tree_size = trees[y][x] as usize;
-            } else if trees[y][x] > current_tree_size as u32 {
-                current_tree_size = trees[y][x] as usize;
-                visible_trees[y][x] = true;
-            }
-        }
-    }
-    for y in (0..trees.len()).rev() {
-        let mut current_tree_size = 0;
-        for x in (0..trees[0].len()).rev() {
-            if x == trees.len() - 1 {
-                current_tree_size = trees[y][x] as usize;
-            } else if trees[y][x] > current_tree_size as u32 {
-                current_tree_size = trees[y][x] as usize;
-                visible_trees[y][x] = true;
-            }
-        }
-    }
+use aoc_core::{ParseError, Result, Solution};
+use std::iter;
 
-    /// Iterations for Ys
-    for x in 0..trees.len() {
-        let mut current_tree_size = 0;
-        for y in 0..trees[0].len() {
-            if y == 0 {
-                current_tree_size = trees[y][x] as usize;
-            } else if trees[y][x] > current_tree_size as u32 {
-                current_tree_size = trees[y][x] as usize;
-                visible_trees[y][x] = true;
-            }
-        }
-    }
-    for x in (0..trees.len()).rev() {
-        let mut current_tree_size = 0;
-        for y in (0..trees[0].len()).rev() {
-            if y == trees.len() - 1 {
-                current_tree_size = trees[y][x] as usize;
-            } else if trees[y][x] > current_tree_size as u32 {
-                current_tree_size = trees[y][x] as usize;
-                visible_trees[y][x] = true;
-            }
-        }
-    }
+use geometry::Direction;
+use grid::{Grid, Pos};
+use tracing::{debug, trace};
+use visualize::Rgb;
 
-    dbg!(&visible_trees);
-    visible_trees.iter().flatten().filter(|&&v| v).count()
-}
+pub struct Day8;
 
-pub fn part_2(input: &str) -> u32 {
-    let (_, trees) = parse_trees(input).unwrap();
+impl Solution for Day8 {
+    type Input<'a> = Grid<u32>;
+    type Part1 = usize;
+    type Part2 = u32;
 
-    let y_max = trees.len();
-    let x_max = trees[0].len();
+    fn parse(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, |height| height.to_digit(10))
+    }
 
-    let mut high_score = 0;
-    for (y_index, tree_line) in trees.iter().enumerate() {
-        for (x_index, &tree_house_height) in tree_line.iter().enumerate() {
-            let mut scores = vec![0; 4];
+    fn part_1(trees: &Grid<u32>) -> Result<usize> {
+        let (width, height) = (trees.width(), trees.height());
+        let mut visible_trees = Grid::filled(width, height, false);
 
-            for x_position in (0..x_index).rev() {
-                if trees[y_index][x_position] < tree_house_height {
-                    scores[0] += 1;
-                } else {
-                    scores[0] += 1;
-                    break;
-                }
-            }
+        // Walk in from every edge tree, marking each tree taller than all before it.
+        for (edge, direction) in edges(trees) {
+            let mut current_tree_size = trees[edge];
+            visible_trees[edge] = true;
 
-            for x_position in (x_index + 1)..x_max {
-                if trees[y_index][x_position] < tree_house_height {
-                    scores[1] += 1;
-                } else {
-                    scores[1] += 1;
-                    break;
+            for (pos, &tree) in trees.ray(edge, direction) {
+                if tree > current_tree_size {
+                    current_tree_size = tree;
+                    visible_trees[pos] = true;
                 }
             }
+        }
 
-            for y_position in (0..y_index).rev() {
-                if trees[y_position][x_index] < tree_house_height {
-                    scores[2] += 1;
-                } else {
-                    scores[2] += 1;
-                    break;
-                }
+        // The height map, then the trees seen from outside it.
+        visualize::push(|| trees.map(|&tree| shade(tree, 9)));
+        visualize::push(|| {
+            let mut frame = trees.map(|&tree| shade(tree, 9));
+            for (pos, _) in visible_trees.iter().filter(|(_, &visible)| visible) {
+                frame[pos] = [0, 255, 0];
             }
+            frame
+        });
+        trace!(
+            "visible trees\n{}",
+            visible_trees.render(|&visible| if visible { '#' } else { '.' })
+        );
+        Ok(visible_trees.iter().filter(|(_, &v)| v).count())
+    }
 
-            for y_position in (y_index + 1)..y_max {
-                if trees[y_position][x_index] < tree_house_height {
-                    scores[3] += 1;
-                } else {
-                    scores[3] += 1;
-                    break;
-                }
-            }
+    fn part_2(trees: &Grid<u32>) -> Result<u32> {
+        let mut scores = Grid::filled(trees.width(), trees.height(), 1);
+
+        // Walk in from every edge, and look back from each tree towards it.
+        // `blocking` holds the trees that could still end a later tree's view,
+        // nearest last: any tree behind a taller one never can.
+        for (edge, direction) in edges(trees) {
+            let mut blocking: Vec<(u32, u32)> = Vec::new();
 
-            let scenic_score = scores.iter().product::<u32>();
+            let line = iter::once((edge, &trees[edge])).chain(trees.ray(edge, direction));
+            for (distance_from_edge, (pos, &tree)) in (0..).zip(line) {
+                while blocking.last().is_some_and(|&(_, height)| height < tree) {
+                    blocking.pop();
+                }
 
-            if scenic_score > high_score {
-                high_score = scenic_score;
+                let viewing_distance = match blocking.last() {
+                    Some(&(blocker, _)) => distance_from_edge - blocker,
+                    None => distance_from_edge,
+                };
+                scores[pos] *= viewing_distance;
+                blocking.push((distance_from_edge, tree));
             }
         }
+
+        let best = scores.iter().max_by_key(|(_, &score)| score);
+        visualize::push(|| {
+            // Scores span orders of magnitude, so shade by their square root.
+            let top = best.map_or(0, |(_, &score)| score);
+            scores.map(|&score| shade(score.isqrt(), top.isqrt()))
+        });
+        debug!(tree_house = ?best.map(|(pos, _)| pos), "best scenic score");
+
+        Ok(best.map_or(0, |(_, &score)| score))
     }
+}
 
-    high_score
+/// Grey from black at 0 to white at `max`, in 16 steps so a GIF of the
+/// map stays within its colour limit.
+fn shade(value: u32, max: u32) -> Rgb {
+    let level = (u64::from(value) * 15 / u64::from(max.max(1))) as u8;
+    [level * 17; 3]
 }
 
-fn parse_trees(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
-    let (input, vecs) = separated_list1(
-        newline,
-        digit1.map(|nums: &str| nums.chars().map(|num| num.to_digit(10).unwrap()).collect()),
-    )(input)?;
-    Ok((input, vecs))
+/// Every edge tree with the direction into the grid, so each row and column
+/// is walked both ways.
+fn edges(trees: &Grid<u32>) -> impl Iterator<Item = (Pos, Direction)> {
+    let (width, height) = (trees.width(), trees.height());
+
+    (0..height)
+        .flat_map(move |y| {
+            [
+                ((0, y), Direction::Right),
+                ((width - 1, y), Direction::Left),
+            ]
+        })
+        .chain(
+            (0..width)
+                .flat_map(move |x| [((x, 0), Direction::Down), ((x, height - 1), Direction::Up)]),
+        )
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::{collection::vec, prelude::*};
+
     use super::*;
 
     const INPUT: &str = "30373
@@ -155,13 +125,59 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        let result = part_1(&INPUT);
+        let result = Day8::part_1(&Day8::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, 21);
     }
 
+    #[test]
+    fn test_rectangular_grid() {
+        let trees = Day8::parse("3037\n2551\n6533").unwrap();
+        assert_eq!(Day8::part_1(&trees).unwrap(), 12);
+        assert_eq!(Day8::part_2(&trees).unwrap(), 1);
+    }
+
     #[test]
     fn test_part_2() {
-        let result = part_2(&INPUT);
+        let result = Day8::part_2(&Day8::parse(INPUT).unwrap()).unwrap();
         assert_eq!(result, 8);
     }
+
+    /// Scans outwards from every tree, as the solution first did.
+    fn reference(trees: &Grid<u32>) -> u32 {
+        let viewing_distance = |pos, direction| {
+            let mut distance = 0;
+            for (_, &tree) in trees.ray(pos, direction) {
+                distance += 1;
+                if tree >= trees[pos] {
+                    break;
+                }
+            }
+            distance
+        };
+
+        trees
+            .positions()
+            .map(|pos| {
+                Direction::ORTHOGONAL
+                    .iter()
+                    .map(|&direction| viewing_distance(pos, direction))
+                    .product::<u32>()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_reference(
+            (width, heights) in (1..12usize).prop_flat_map(|width| {
+                (Just(width), vec(0..10u32, width..=width * 12))
+            })
+        ) {
+            let height = heights.len() / width;
+            let trees = Grid::new(width, height, heights[..width * height].to_vec());
+
+            prop_assert_eq!(Day8::part_2(&trees).unwrap(), reference(&trees));
+        }
+    }
 }
@@ -1,165 +1,112 @@
-use itertools::Itertools;
-use std::collections::HashSet;
+use std::{collections::HashSet, iter};
 
+use aoc_core::{finish, ParseError, Result, Solution};
+use geometry::{Bounds, Direction, Point};
+use grid::Grid;
 use nom::branch::alt;
-use nom::bytes::streaming::tag;
-use nom::multi::separated_list1;
+use nom::bytes::complete::tag;
+use nom::combinator::cut;
 use nom::sequence::separated_pair;
 use nom::Parser;
-use nom::{
-    character::complete::{self, newline},
-    IResult,
-};
-
-use ::lending_iterator::prelude::*;
-
-pub fn part_1(input: &str) -> usize {
-    let (_, moves_set) = moves(input).unwrap();
-    let mut head = (0, 0);
-    let mut tail = (0, 0);
-    let mut tail_positions = HashSet::from([tail]);
-
-    for head_move in moves_set.iter() {
-        match head_move {
-            Direction::Left => {
-                head.0 -= 1;
-            }
-            Direction::Right => {
-                head.0 += 1;
-            }
-            Direction::Down => {
-                head.1 -= 1;
-            }
-            Direction::Up => {
-                head.1 += 1;
-            }
-        }
+use nom::{character::complete, IResult};
+use parsers::{lines, number};
+use tracing::{info, trace};
+use visualize::Frame;
 
-        let x_range = (head.0 - 1)..=(head.0 + 1);
-        let y_range = (head.1 - 1)..=(head.1 + 1);
+pub struct Day9;
 
-        let tail_is_connected = x_range
-            .cartesian_product(y_range)
-            .any(|tuple| tuple == tail);
+impl Solution for Day9 {
+    type Input<'a> = Vec<(Direction, u32)>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-        if !tail_is_connected {
-            let mut new_tail = head.clone();
-            match head_move {
-                Direction::Left => {
-                    new_tail.0 += 1;
-                }
-                Direction::Right => {
-                    new_tail.0 -= 1;
-                }
-                Direction::Down => {
-                    new_tail.1 += 1;
-                }
-                Direction::Up => {
-                    new_tail.1 -= 1;
-                }
-            }
-            tail = new_tail;
-            tail_positions.insert(new_tail);
-        }
+    fn parse(input: &str) -> Result<Vec<(Direction, u32)>, ParseError> {
+        finish(input, moves(input))
     }
 
-    tail_positions.len()
-}
-
-pub fn part_2(input: &str) -> usize {
-    let (_, moves_set) = moves(input).unwrap();
+    fn part_1(moves_set: &Vec<(Direction, u32)>) -> Result<usize> {
+        Ok(tail_positions(moves_set, 2))
+    }
 
-    let mut rope = [(0, 0); 10];
-    let mut tail_positions = HashSet::from([*rope.last().unwrap()]);
+    fn part_2(moves_set: &Vec<(Direction, u32)>) -> Result<usize> {
+        Ok(tail_positions(moves_set, 10))
+    }
+}
 
-    for head_move in moves_set.iter() {
-        match head_move {
-            Direction::Left => {
-                rope[0].0 -= 1;
-            }
-            Direction::Right => {
-                rope[0].0 += 1;
-            }
-            Direction::Down => {
-                rope[0].1 -= 1;
-            }
-            Direction::Up => {
-                rope[0].1 += 1;
+/// Roughly how many frames a recording of the rope gets, however long the input.
+const FRAMES: usize = 200;
+
+/// Number of distinct positions the last of `knots` visits while the head
+/// follows `moves_set`, one step at a time.
+fn tail_positions(moves_set: &[(Direction, u32)], knots: usize) -> usize {
+    let mut rope = vec![Point::ORIGIN; knots];
+    let mut tail_positions = HashSet::from([Point::ORIGIN]);
+    // The tail's new positions in the order it reaches them, and the rope
+    // with how much of that trail it has left, every few moves.
+    let mut trail = vec![Point::ORIGIN];
+    let mut snapshots = Vec::new();
+    let every = moves_set.len().div_ceil(FRAMES).max(1);
+
+    for (index, &(direction, count)) in moves_set.iter().enumerate() {
+        for head_move in iter::repeat_n(direction, count as usize) {
+            rope[0] += head_move;
+
+            for knot in 1..rope.len() {
+                let head = rope[knot - 1];
+                let tail = &mut rope[knot];
+
+                // A knot that no longer touches the one ahead of it moves one step
+                // towards it on each axis, diagonally if they share neither.
+                if head.chebyshev(*tail) > 1 {
+                    *tail += (head - *tail).signum();
+                }
             }
-        }
 
-        let mut rope_windows = rope.windows_mut::<2>();
-
-        while let Some([ref mut head, ref mut tail]) = rope_windows.next() {
-            let x_range = (head.0 - 1)..=(head.0 + 1);
-            let y_range = (head.1 - 1)..=(head.1 + 1);
-
-            let tail_is_connected = x_range
-                .cartesian_product(y_range)
-                .any(|tuple| tuple == *tail);
-
-            if !tail_is_connected {
-                if head.0 == tail.0 {
-                    if head.1 > tail.1 {
-                        tail.1 += 1;
-                    } else {
-                        tail.1 -= 1;
-                    }
-                } else if head.1 == tail.1 {
-                    if head.0 > tail.0 {
-                        tail.0 += 1;
-                    } else {
-                        tail.0 -= 1;
-                    }
-                } else {
-                    let x_range = (head.0 - 1)..=(head.0 + 1);
-                    let y_range = (head.1 - 1)..=(head.1 + 1);
-
-                    let head_3x3 = x_range.cartesian_product(y_range).collect::<Vec<_>>();
-
-                    let x_range = (tail.0 - 1)..=(tail.0 + 1);
-                    let y_range = (tail.1 - 1)..=(tail.1 + 1);
-
-                    let maybe_new_tail: Vec<(i32, i32)> = x_range
-                        .cartesian_product(y_range)
-                        .filter(|tuple| head_3x3.contains(tuple))
-                        .collect();
-                    match maybe_new_tail.len() {
-                        2 => {
-                            let new_head_cross_positions = [
-                                (head.0 - 1, head.1),
-                                (head.0 + 1, head.1),
-                                (head.0, head.1 - 1),
-                                (head.0, head.1 + 1),
-                            ];
-                            let next = maybe_new_tail
-                                .iter()
-                                .find(|tuple| new_head_cross_positions.contains(tuple))
-                                .unwrap();
-                            *tail = *next;
-                        }
-                        1 => {
-                            *tail = maybe_new_tail[0];
-                        }
-                        _ => {
-                            panic!("unknown tail length");
-                        }
-                    };
-                }
+            trace!(?head_move, head = ?rope[0], tail = ?rope[knots - 1]);
+            if tail_positions.insert(rope[knots - 1]) && visualize::recording() {
+                trail.push(rope[knots - 1]);
             }
         }
-        tail_positions.insert(*rope.last().unwrap());
+        if index % every == 0 && visualize::recording() {
+            snapshots.push((rope.clone(), trail.len()));
+        }
     }
 
+    let seen = snapshots
+        .iter()
+        .flat_map(|(rope, _)| rope)
+        .chain(&trail)
+        .copied();
+    let bounds = Bounds::of(seen.chain([Point::ORIGIN])).expect("the origin is always in bounds");
+    for (rope, trail_length) in &snapshots {
+        visualize::push(|| draw(rope, &trail[..*trail_length], bounds));
+    }
+    info!(knots, visited = tail_positions.len(), "simulated rope");
     tail_positions.len()
 }
 
-#[derive(Clone, Copy, Debug)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
+/// The tail's trail so far in grey and the rope over it, from a red head to a
+/// yellow tail, on a canvas spanning `bounds` so every frame lines up.
+fn draw(rope: &[Point], trail: &[Point], bounds: Bounds) -> Frame {
+    let cell = |point: &Point| {
+        (
+            (point.x - bounds.min.x) as usize,
+            (point.y - bounds.min.y) as usize,
+        )
+    };
+
+    let mut frame = Grid::filled(
+        bounds.width() as usize,
+        bounds.height() as usize,
+        visualize::BACKGROUND,
+    );
+    for point in trail {
+        frame[cell(point)] = [90, 90, 90];
+    }
+    for (knot, point) in rope.iter().enumerate().rev() {
+        frame[cell(point)] = [255, 40 + (knot * 160 / rope.len()) as u8, 40];
+    }
+    frame
 }
 
 fn direction(input: &str) -> IResult<&str, Direction> {
@@ -173,16 +120,8 @@ fn direction(input: &str) -> IResult<&str, Direction> {
     Ok((input, direction))
 }
 
-fn moves(input: &str) -> IResult<&str, Vec<Direction>> {
-    let (input, vecs) =
-        separated_list1(newline, separated_pair(direction, tag(" "), complete::u32))(input)?;
-
-    let vecs = vecs
-        .iter()
-        .flat_map(|(dir, repeat)| vec![*dir; *repeat as usize])
-        .collect();
-
-    Ok((input, vecs))
+fn moves(input: &str) -> IResult<&str, Vec<(Direction, u32)>> {
+    lines(separated_pair(direction, cut(tag(" ")), cut(number)))(input)
 }
 
 #[cfg(test)]
@@ -200,16 +139,17 @@ R 2";
 
     #[test]
     fn test_part1() {
-        assert_eq!(part_1(INPUT), 13)
+        assert_eq!(Day9::part_1(&Day9::parse(INPUT).unwrap()).unwrap(), 13)
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part_2(INPUT), 1);
+        assert_eq!(Day9::part_2(&Day9::parse(INPUT).unwrap()).unwrap(), 1);
 
         assert_eq!(
-            part_2(
-                "R 5
+            Day9::part_2(
+                &Day9::parse(
+                    "R 5
 U 8
 L 8
 D 3
@@ -217,7 +157,10 @@ R 17
 D 10
 L 25
 U 20"
-            ),
+                )
+                .unwrap()
+            )
+            .unwrap(),
             36
         )
     }
@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc_core::Solution;
+use libfuzzer_sys::fuzz_target;
+
+// Any text parses or fails with a located error, never panics.
+fuzz_target!(|input: &str| {
+    if let Err(error) = day_11::Day11::parse(input) {
+        error.render();
+    }
+});
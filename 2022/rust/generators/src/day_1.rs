@@ -0,0 +1,17 @@
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// `size` elves carrying one to six snacks each, separated by blank lines.
+pub fn calories(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..rng.gen_range(1..=6))
+                .map(|_| rng.gen_range(1000..=60000).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n"
+}
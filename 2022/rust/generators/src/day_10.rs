@@ -0,0 +1,16 @@
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// `size` instructions, about a third of them `noop`.
+pub fn program(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            if rng.gen_ratio(1, 3) {
+                "noop\n".to_string()
+            } else {
+                format!("addx {}\n", rng.gen_range(-20..=20))
+            }
+        })
+        .collect()
+}
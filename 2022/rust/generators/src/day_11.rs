@@ -0,0 +1,56 @@
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+/// Divisors are distinct primes, as in the real inputs. Their product squared
+/// must fit in 64 bits for `old * old` to be computed.
+const PRIMES: [u64; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+/// Two to eight monkeys holding `size` items between them, at least one
+/// each since the puzzle never lists an empty `Starting items`. At most one
+/// monkey squares the worry level.
+pub fn monkeys(rng: &mut Rng, size: usize) -> String {
+    let count = rng.gen_range(2..=PRIMES.len());
+    let mut divisors = PRIMES;
+    divisors.shuffle(rng);
+    let squarer = rng.gen_range(0..count);
+
+    let mut items: Vec<Vec<String>> = (0..count)
+        .map(|_| vec![rng.gen_range(50..100).to_string()])
+        .collect();
+    for _ in count..size {
+        items[rng.gen_range(0..count)].push(rng.gen_range(50..100).to_string());
+    }
+
+    (0..count)
+        .map(|monkey| {
+            let operation = if monkey == squarer {
+                "old * old".to_string()
+            } else if rng.gen_bool(0.5) {
+                format!("old * {}", rng.gen_range(2..=19))
+            } else {
+                format!("old + {}", rng.gen_range(1..=8))
+            };
+            let if_true = (monkey + rng.gen_range(1..count)) % count;
+            let if_false = loop {
+                let other = (monkey + rng.gen_range(1..count)) % count;
+                if other != if_true || count == 2 {
+                    break other;
+                }
+            };
+
+            format!(
+                "Monkey {monkey}:
+  Starting items: {}
+  Operation: new = {operation}
+  Test: divisible by {}
+    If true: throw to monkey {if_true}
+    If false: throw to monkey {if_false}
+",
+                items[monkey].join(", "),
+                divisors[monkey]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,14 @@
+use rand::seq::SliceRandom;
+
+use crate::Rng;
+
+/// `size` rounds of an opponent's move and a response.
+pub fn strategy_guide(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let opponent = ['A', 'B', 'C'].choose(rng).unwrap();
+            let response = ['X', 'Y', 'Z'].choose(rng).unwrap();
+            format!("{opponent} {response}\n")
+        })
+        .collect()
+}
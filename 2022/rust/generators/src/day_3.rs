@@ -0,0 +1,39 @@
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+/// `size` groups of three rucksacks. Each rucksack has exactly one item type
+/// in both compartments, and each group exactly one badge all three carry.
+pub fn rucksacks(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        items.shuffle(rng);
+        let badge = items.pop().unwrap();
+
+        // Split the other 51 items between the elves, so only the badge is
+        // shared by all three: 8 for each compartment plus one for both.
+        for pool in items.chunks(17) {
+            let (shared, pool) = pool.split_first().unwrap();
+            let (first, second) = pool.split_at(8);
+            let length = rng.gen_range(2..=16);
+
+            let mut first: Vec<char> = compartment(rng, first, length - 2);
+            first.extend([*shared, badge]);
+            first.shuffle(rng);
+            let mut second = compartment(rng, second, length - 1);
+            second.push(*shared);
+            second.shuffle(rng);
+
+            input.extend(first);
+            input.extend(second);
+            input.push('\n');
+        }
+    }
+    input
+}
+
+fn compartment(rng: &mut Rng, pool: &[char], length: usize) -> Vec<char> {
+    (0..length).map(|_| *pool.choose(rng).unwrap()).collect()
+}
@@ -0,0 +1,18 @@
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// `size` pairs of section ranges. Sections grow with `size`, so large inputs
+/// also have long ranges.
+pub fn section_pairs(rng: &mut Rng, size: usize) -> String {
+    let last_section = size.max(99) as u32;
+    let mut range = || {
+        let start = rng.gen_range(1..=last_section);
+        let end = rng.gen_range(start..=last_section);
+        format!("{start}-{end}")
+    };
+
+    (0..size)
+        .map(|_| format!("{},{}\n", range(), range()))
+        .collect()
+}
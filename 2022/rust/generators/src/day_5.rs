@@ -0,0 +1,50 @@
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// A drawing of up to nine stacks, then `size` moves that never take more
+/// crates than a stack holds.
+pub fn crates(rng: &mut Rng, size: usize) -> String {
+    let stacks = rng.gen_range(3..=9);
+    // As tall as the real inputs, and taller for larger ones.
+    let tallest = 8 + size / 50;
+
+    let mut heights: Vec<usize> = (0..stacks).map(|_| rng.gen_range(1..=tallest)).collect();
+    heights[rng.gen_range(0..stacks)] = tallest;
+    let mut piles: Vec<usize> = heights.clone();
+
+    let mut input = String::new();
+    for row in (0..tallest).rev() {
+        let cells: Vec<String> = heights
+            .iter()
+            .map(|&height| {
+                if row < height {
+                    format!("[{}]", rng.gen_range('A'..='Z'))
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect();
+        input += &cells.join(" ");
+        input.push('\n');
+    }
+    let labels: Vec<String> = (1..=stacks).map(|stack| format!(" {stack} ")).collect();
+    input += &labels.join(" ");
+    input += "\n\n";
+
+    for _ in 0..size {
+        let from = loop {
+            let from = rng.gen_range(0..stacks);
+            if piles[from] > 0 {
+                break from;
+            }
+        };
+        let to = (from + rng.gen_range(1..stacks)) % stacks;
+        let number = rng.gen_range(1..=piles[from]);
+
+        piles[from] -= number;
+        piles[to] += number;
+        input += &format!("move {number} from {} to {}\n", from + 1, to + 1);
+    }
+    input
+}
@@ -0,0 +1,17 @@
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+/// About `size` characters that only use three letters, then fourteen distinct
+/// ones, so both markers are found late in the stream, then a random tail.
+pub fn datastream(rng: &mut Rng, size: usize) -> String {
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    letters.shuffle(rng);
+    let noise = &letters[..3];
+
+    let mut stream: String = (0..size).map(|_| *noise.choose(rng).unwrap()).collect();
+    stream.extend(&letters[..14]);
+    stream.extend((0..size / 4).map(|_| rng.gen_range('a'..='z')));
+    stream.push('\n');
+    stream
+}
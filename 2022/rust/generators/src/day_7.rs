@@ -0,0 +1,60 @@
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// The terminal output of exploring a tree of `size` directories, each listed
+/// once. The total stays below the 70,000,000 bytes of the device.
+pub fn transcript(rng: &mut Rng, size: usize) -> String {
+    // parents[dir] is the parent of every directory but the root.
+    let parents: Vec<usize> = (1..size).map(|dir| rng.gen_range(0..dir)).collect();
+    let mut children = vec![Vec::new(); size];
+    for (dir, &parent) in (1..).zip(&parents) {
+        children[parent].push(dir);
+    }
+
+    let files = size * 3;
+    let largest_file = (69_000_000 / files).clamp(1, 300_000);
+
+    let mut output = String::from("$ cd /\n");
+    explore(rng, &children, 0, largest_file, &mut output);
+    output
+}
+
+fn explore(
+    rng: &mut Rng,
+    children: &[Vec<usize>],
+    dir: usize,
+    largest_file: usize,
+    output: &mut String,
+) {
+    output.push_str("$ ls\n");
+    for &child in &children[dir] {
+        output.push_str(&format!("dir {}\n", name(child)));
+    }
+    // At least one file, so no directory is listed empty.
+    for file in 0..rng.gen_range(1..=5) {
+        let size = rng.gen_range(1..=largest_file);
+        let extension = [".txt", ".dat", ".log", ""][rng.gen_range(0..4)];
+        output.push_str(&format!("{size} {}{extension}\n", name(file)));
+    }
+
+    for &child in &children[dir] {
+        output.push_str(&format!("$ cd {}\n", name(child)));
+        explore(rng, children, child, largest_file, output);
+        output.push_str("$ cd ..\n");
+    }
+}
+
+/// A lowercase name for a number: a, b, ..., z, ba, bb, ...
+fn name(mut n: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'a' + (n % 26) as u8);
+        n /= 26;
+        if n == 0 {
+            break;
+        }
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
@@ -0,0 +1,18 @@
+use rand::Rng as _;
+
+use crate::Rng;
+
+/// A map of tree heights `size` trees wide and about two thirds as tall.
+pub fn trees(rng: &mut Rng, size: usize) -> String {
+    let height = (size * 2 / 3).max(1);
+
+    (0..height)
+        .map(|_| {
+            let mut row: String = (0..size)
+                .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
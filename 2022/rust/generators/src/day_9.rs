@@ -0,0 +1,13 @@
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::Rng;
+
+/// `size` head moves of one to twenty steps.
+pub fn rope_moves(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let direction = ['L', 'R', 'U', 'D'].choose(rng).unwrap();
+            format!("{direction} {}\n", rng.gen_range(1..=20))
+        })
+        .collect()
+}
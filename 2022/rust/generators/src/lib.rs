@@ -0,0 +1,62 @@
+//! Seeded generators of valid puzzle inputs of any size, for stress and scale
+//! testing. The same seed and size always give the same input.
+
+use rand::SeedableRng;
+
+mod day_1;
+mod day_10;
+mod day_11;
+mod day_2;
+mod day_3;
+mod day_4;
+mod day_5;
+mod day_6;
+mod day_7;
+mod day_8;
+mod day_9;
+
+pub type Rng = rand_pcg::Pcg64;
+
+/// Writes an input with about `size` records: elves, lines, moves, directories,
+/// grid columns and so on, depending on the day.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+pub fn generator(year: u16, day: u8) -> Option<Generator> {
+    let generator: Generator = match (year, day) {
+        (2022, 1) => day_1::calories,
+        (2022, 2) => day_2::strategy_guide,
+        (2022, 3) => day_3::rucksacks,
+        (2022, 4) => day_4::section_pairs,
+        (2022, 5) => day_5::crates,
+        (2022, 6) => day_6::datastream,
+        (2022, 7) => day_7::transcript,
+        (2022, 8) => day_8::trees,
+        (2022, 9) => day_9::rope_moves,
+        (2022, 10) => day_10::program,
+        (2022, 11) => day_11::monkeys,
+        _ => return None,
+    };
+    Some(generator)
+}
+
+pub fn generate(year: u16, day: u8, seed: u64, size: usize) -> Option<String> {
+    let generator = generator(year, day)?;
+    Some(generator(&mut Rng::seed_from_u64(seed), size.max(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_input() {
+        for day in 1..=11 {
+            let input = generate(2022, day, 7, 40).unwrap();
+
+            assert_eq!(generate(2022, day, 7, 40).unwrap(), input);
+            assert_ne!(generate(2022, day, 8, 40).unwrap(), input, "day {day}");
+            assert!(generate(2022, day, 7, 400).unwrap().len() > input.len());
+        }
+        assert_eq!(generate(2022, 12, 7, 40), None);
+    }
+}
@@ -0,0 +1,278 @@
+use std::ops::{Index, IndexMut};
+
+use aoc_core::{Answer, ParseError};
+use geometry::Direction;
+
+/// Position of a cell as `(x, y)`, with `(0, 0)` the top-left corner.
+pub type Pos = (usize, usize);
+
+/// A rectangular 2D map stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Panics unless there are exactly `width * height` cells, with at least
+    /// one column.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert!(width > 0, "a grid needs at least one column");
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {width}x{height} grid needs {} cells",
+            width * height
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses a character map, one row per line. `cell` turns each character
+    /// into a value, or `None` if it is not allowed.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for line in input.trim_end().lines() {
+            let start = cells.len();
+            for (idx, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    ParseError::at(
+                        input,
+                        &line[idx..idx + c.len_utf8()],
+                        "unexpected character",
+                    )
+                })?;
+                cells.push(value);
+            }
+
+            let row_width = cells.len() - start;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(ParseError::at(
+                        input,
+                        line,
+                        format!("expected {width} cells in every row"),
+                    ));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid::new(width, height, cells)),
+            _ => Err(ParseError::at(input, input, "expected a grid")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.offset(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let offset = self.offset(pos);
+            Some(&mut self.cells[offset])
+        } else {
+            None
+        }
+    }
+
+    /// The cell one step in `direction` from `pos`, if that is still on the grid.
+    pub fn step(&self, (x, y): Pos, direction: Direction) -> Option<Pos> {
+        let offset = direction.offset();
+        let pos = (
+            x.checked_add_signed(offset.x as isize)?,
+            y.checked_add_signed(offset.y as isize)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// Panics if `x` is not a column of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is outside the {}x{} grid",
+            self.width,
+            self.height
+        );
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The cells from `pos` (exclusive) to the edge of the grid in `direction`.
+    pub fn ray(&self, pos: Pos, direction: Direction) -> impl Iterator<Item = (Pos, &T)> {
+        std::iter::successors(self.step(pos, direction), move |&pos| {
+            self.step(pos, direction)
+        })
+        .map(|pos| (pos, &self[pos]))
+    }
+
+    /// The neighbours of `pos` in `directions`, e.g. [`Direction::ORTHOGONAL`]
+    /// or [`Direction::ALL`].
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: Pos,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+        directions
+            .iter()
+            .filter_map(move |&direction| self.step(pos, direction))
+            .map(|pos| (pos, &self[pos]))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Draws the grid back as a character map, without a trailing newline.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut cell).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn offset(&self, (x, y): Pos) -> usize {
+        y * self.width + x
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {width}x{height} grid"))
+    }
+}
+
+/// A picture answer, with lit cells drawn as `#`.
+impl From<Grid<bool>> for Answer {
+    fn from(grid: Grid<bool>) -> Self {
+        Answer::Image(
+            grid.rows()
+                .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#..\r\n.#.\r\n";
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = Grid::parse(MAP, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.column(1).collect::<Vec<_>>(), [&false, &true]);
+        assert_eq!(
+            grid.render(|&wall| if wall { '#' } else { '.' }),
+            "#..\n.#."
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Grid::parse("12\n3x\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let error = Grid::parse("12\n345\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(error.message, "expected 2 cells in every row");
+
+        let error = Grid::parse("\n\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(error.message, "expected a grid");
+    }
+
+    #[test]
+    fn test_rays_and_neighbours() {
+        let grid = Grid::new(3, 2, (0..6).collect());
+
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(
+            grid.ray((0, 1), Direction::Right).collect::<Vec<_>>(),
+            [((1, 1), &4), ((2, 1), &5)]
+        );
+        assert_eq!(grid.ray((0, 0), Direction::Up).count(), 0);
+        assert_eq!(grid.column(2).collect::<Vec<_>>(), [&2, &5]);
+        assert_eq!(
+            grid.neighbours((0, 0), &Direction::ALL)
+                .map(|(_, &v)| v)
+                .collect::<Vec<_>>(),
+            [3, 1, 4]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside the 3x2 grid")]
+    fn test_column_outside_the_grid() {
+        let _ = Grid::new(3, 2, (0..6).collect()).column(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "a grid needs at least one column")]
+    fn test_zero_width() {
+        Grid::<u8>::filled(0, 2, 0);
+    }
+}
@@ -0,0 +1,114 @@
+//! nom combinators for the input shapes most puzzles share. Line breaks may be
+//! `\n` or `\r\n`; a trailing line break is left for `aoc_core::finish` to skip.
+
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::{is_not, tag},
+    character::complete::{char, digit1, line_ending, satisfy, space0},
+    combinator::{cut, eof, map, map_res, opt, peek, recognize},
+    error::Error,
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    IResult, Parser,
+};
+
+/// One `item` per line. Each item must take up its whole line.
+pub fn lines<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(line_ending, terminated(item, peek(end_of_line)))
+}
+
+/// Groups of lines separated by a blank line, e.g. one elf's snacks per block.
+pub fn blocks<'a, O, F>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(pair(line_ending, line_ending), block)
+}
+
+/// An integer of any width, with an optional minus sign.
+pub fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
+
+/// Numbers separated by commas, with optional spaces around them: `79, 98`.
+pub fn numbers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(delimited(space0, char(','), space0), number)(input)
+}
+
+/// A line of single-digit numbers, e.g. `30373`.
+pub fn digits(input: &str) -> IResult<&str, Vec<u8>> {
+    many1(map(satisfy(|c| c.is_ascii_digit()), |c| c as u8 - b'0'))(input)
+}
+
+/// Rows of single-digit numbers, such as a height map.
+pub fn digit_grid(input: &str) -> IResult<&str, Vec<Vec<u8>>> {
+    lines(digits)(input)
+}
+
+/// A `key: value` line for a known `key`, possibly indented:
+/// `  Test: divisible by 19`. Once the key matches, the value must follow.
+pub fn field<'a, O, F>(key: &'a str, value: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    preceded(tuple((space0, tag(key), char(':'), space0)), cut(value))
+}
+
+/// A `key: value` line with any key, possibly indented.
+pub fn key_value<'a, O, F>(value: F) -> impl FnMut(&'a str) -> IResult<&'a str, (&'a str, O)>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    preceded(
+        space0,
+        separated_pair(is_not(":\r\n"), pair(char(':'), space0), value),
+    )
+}
+
+fn end_of_line(input: &str) -> IResult<&str, &str> {
+    nom::branch::alt((line_ending, eof))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_of_lines() {
+        let input = "1000\r\n2000\r\n\r\n-3000\r\n";
+        let (rest, elves) = blocks(lines(number::<i32>))(input).unwrap();
+
+        assert_eq!(elves, [vec![1000, 2000], vec![-3000]]);
+        assert_eq!(rest, "\r\n");
+    }
+
+    #[test]
+    fn test_lines_need_whole_lines() {
+        let (rest, values) = lines(number::<u32>)("12\n3x\n").unwrap();
+
+        assert_eq!(values, [12]);
+        assert_eq!(rest, "\n3x\n");
+        assert!(number::<u8>("300").is_err());
+        assert_eq!(digit_grid("303\n255").unwrap().1, [[3, 0, 3], [2, 5, 5]]);
+    }
+
+    #[test]
+    fn test_records() {
+        let (_, items) =
+            field("Starting items", numbers::<u64>)("  Starting items: 79, 98").unwrap();
+        assert_eq!(items, [79, 98]);
+
+        assert!(matches!(
+            field("Test", number::<u64>)("Test: x"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert_eq!(
+            key_value(number::<u32>)("  size: 42").unwrap().1,
+            ("size", 42)
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use crate::{Frame, Rgb, BACKGROUND};
+
+/// Writes `frames` as a looping animated GIF, each cell `scale` pixels
+/// square and each frame shown for `delay` hundredths of a second. Frames
+/// smaller than the largest are drawn in its top-left corner. GIF allows at
+/// most 256 colours across all the frames.
+pub fn gif(frames: &[Frame], scale: usize, delay: u16, out: impl Write) -> io::Result<()> {
+    let width = frames.iter().map(|frame| frame.width()).max().unwrap_or(0) * scale;
+    let height = frames.iter().map(|frame| frame.height()).max().unwrap_or(0) * scale;
+    let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(invalid(format!(
+            "a {width}x{height} GIF is too large, try a smaller scale"
+        )));
+    };
+    if width == 0 || height == 0 {
+        return Err(invalid("there are no frames to draw".to_string()));
+    }
+
+    let palette = Palette::of(frames)?;
+    let mut encoder = ::gif::Encoder::new(out, width, height, &palette.flat()).map_err(encoding)?;
+    encoder
+        .set_repeat(::gif::Repeat::Infinite)
+        .map_err(encoding)?;
+
+    for frame in frames {
+        let mut pixels = vec![0; usize::from(width) * usize::from(height)];
+        for ((x, y), colour) in frame.iter() {
+            let index = palette.index[colour];
+            for row in y * scale..(y + 1) * scale {
+                let start = row * usize::from(width) + x * scale;
+                pixels[start..start + scale].fill(index);
+            }
+        }
+
+        let frame = ::gif::Frame {
+            width,
+            height,
+            delay,
+            buffer: Cow::Owned(pixels),
+            ..Default::default()
+        };
+        encoder.write_frame(&frame).map_err(encoding)?;
+    }
+    Ok(())
+}
+
+/// Writes `frame` as an RGB PNG, each cell `scale` pixels square.
+pub fn png(frame: &Frame, scale: usize, out: impl Write) -> io::Result<()> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let (Ok(png_width), Ok(png_height)) = (u32::try_from(width), u32::try_from(height)) else {
+        return Err(invalid(format!("a {width}x{height} PNG is too large")));
+    };
+
+    let mut pixels = Vec::with_capacity(width * height * 3);
+    for row in frame.rows() {
+        let line: Vec<u8> = row.iter().flat_map(|colour| colour.repeat(scale)).collect();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&line);
+        }
+    }
+
+    let mut encoder = ::png::Encoder::new(out, png_width, png_height);
+    encoder.set_color(::png::ColorType::Rgb);
+    encoder.set_depth(::png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Every colour used in a recording, the background first.
+struct Palette {
+    colours: Vec<Rgb>,
+    index: HashMap<Rgb, u8>,
+}
+
+impl Palette {
+    fn of(frames: &[Frame]) -> io::Result<Palette> {
+        let mut palette = Palette {
+            colours: Vec::new(),
+            index: HashMap::new(),
+        };
+        let cells = frames
+            .iter()
+            .flat_map(|frame| frame.iter().map(|(_, &colour)| colour));
+        for colour in std::iter::once(BACKGROUND).chain(cells) {
+            if palette.index.contains_key(&colour) {
+                continue;
+            }
+            let index = u8::try_from(palette.colours.len())
+                .map_err(|_| invalid("a GIF can only use 256 colours".to_string()))?;
+            palette.index.insert(colour, index);
+            palette.colours.push(colour);
+        }
+        Ok(palette)
+    }
+
+    fn flat(&self) -> Vec<u8> {
+        self.colours.concat()
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn encoding(error: ::gif::EncodingError) -> io::Error {
+    match error {
+        ::gif::EncodingError::Io(error) => error,
+        error => io::Error::other(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use grid::Grid;
+
+    use super::*;
+
+    const RED: Rgb = [255, 0, 0];
+    const WHITE: Rgb = [255, 255, 255];
+
+    #[test]
+    fn test_gif_pads_frames_to_the_largest() {
+        let frames = [Grid::filled(1, 1, RED), Grid::new(2, 1, vec![WHITE, RED])];
+        let mut out = Vec::new();
+        gif(&frames, 2, 10, &mut out).unwrap();
+
+        let mut options = ::gif::DecodeOptions::new();
+        options.set_color_output(::gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(out.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (4, 2));
+
+        let first = decoder.read_next_frame().unwrap().unwrap().buffer.to_vec();
+        assert_eq!(
+            &first[..16],
+            [255, 0, 0, 255, 255, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0, 255]
+        );
+        assert_eq!(decoder.read_next_frame().unwrap().unwrap().delay, 10);
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_png_scales_cells() {
+        let frame = Grid::new(2, 1, vec![WHITE, RED]);
+        let mut out = Vec::new();
+        png(&frame, 3, &mut out).unwrap();
+
+        let mut reader = ::png::Decoder::new(out.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (6, 3));
+        assert_eq!(&pixels[9..12], RED);
+        assert_eq!(&pixels[18..21], WHITE);
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+
+use grid::Grid;
+
+pub use export::{gif, png};
+
+mod export;
+
+/// A colour as `[red, green, blue]`.
+pub type Rgb = [u8; 3];
+
+/// One picture of a simulation, a colour per cell.
+pub type Frame = Grid<Rgb>;
+
+/// Fills the space around frames smaller than the largest one.
+pub const BACKGROUND: Rgb = [0, 0, 0];
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static FRAMES: Mutex<Vec<Frame>> = Mutex::new(Vec::new());
+
+/// Whether frames pushed now are kept. Solutions check this before
+/// gathering state that only a picture needs.
+pub fn recording() -> bool {
+    RECORDING.load(Ordering::Relaxed)
+}
+
+/// Keeps the frame `draw` returns, if recording. `draw` is not called otherwise.
+pub fn push(draw: impl FnOnce() -> Frame) {
+    if recording() {
+        let frame = draw();
+        FRAMES
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .push(frame);
+    }
+}
+
+/// Starts keeping pushed frames, dropping any left from an earlier recording.
+pub fn start() {
+    take();
+    RECORDING.store(true, Ordering::Relaxed);
+}
+
+/// Stops recording and returns the frames pushed since [`start`], oldest first.
+pub fn finish() -> Vec<Frame> {
+    RECORDING.store(false, Ordering::Relaxed);
+    take()
+}
+
+fn take() -> Vec<Frame> {
+    std::mem::take(&mut *FRAMES.lock().unwrap_or_else(|error| error.into_inner()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_only_between_start_and_finish() {
+        let frame = || Grid::filled(2, 1, [255, 0, 0]);
+
+        push(|| unreachable!("not recording"));
+        start();
+        push(frame);
+        push(frame);
+        assert_eq!(finish(), vec![frame(), frame()]);
+
+        push(|| unreachable!("not recording"));
+        assert!(finish().is_empty());
+    }
+}